@@ -1,3 +0,0 @@
-fn main() {
-    println!("cargo:rustc-link-arg=-Tlinkall.x");
-}
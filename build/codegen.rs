@@ -0,0 +1,150 @@
+use std::{
+    error::Error,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    fonts::ConvertedFont,
+    images::ConvertedImage,
+    manifest::{FontStyle, ImageFormat},
+};
+
+// Lists the files in `dir` with one of the given extensions as (name, path) pairs, sorted by name
+// so the generated code is stable between builds.
+pub fn asset_files(
+    dir: &Path,
+    extensions: &[&str],
+) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+
+        if extension.is_some_and(|ext| extensions.contains(&ext.as_str()))
+            && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+        {
+            files.push((name.to_string(), path));
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn const_name(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    ident
+}
+
+fn write_blob(out_dir: &Path, name: &str, data: &[u8]) -> Result<String, Box<dyn Error>> {
+    let path = out_dir.join(name);
+    fs::write(&path, data)?;
+
+    Ok(format!("include_bytes!({:?})", path.display().to_string()))
+}
+
+pub fn generate(
+    out_dir: &Path,
+    images: &[ConvertedImage],
+    fonts: &[ConvertedFont],
+) -> Result<String, Box<dyn Error>> {
+    let mut source = String::new();
+
+    writeln!(source, "pub mod images {{")?;
+    for image in images {
+        let ident = const_name(&image.name);
+        let data = write_blob(out_dir, &format!("image_{}.bin", ident), &image.data)?;
+        let mask = match &image.mask {
+            Some(mask) => format!(
+                "Some({})",
+                write_blob(out_dir, &format!("image_{}.mask", ident), mask)?
+            ),
+            None => "None".to_string(),
+        };
+        let format = match image.format {
+            ImageFormat::Rgb565 => "Rgb565",
+            ImageFormat::Rle => "Rle",
+        };
+
+        writeln!(
+            source,
+            "    pub const {}: crate::assets::Image = crate::assets::Image {{ width: {}, height: \
+             {}, format: crate::assets::ImageFormat::{}, data: {}, mask: {} }};",
+            ident, image.width, image.height, format, data, mask
+        )?;
+    }
+
+    writeln!(
+        source,
+        "    pub const ALL: &[(&str, &crate::assets::Image)] = &["
+    )?;
+    for image in images {
+        writeln!(
+            source,
+            "        ({:?}, &{}),",
+            image.name,
+            const_name(&image.name)
+        )?;
+    }
+    writeln!(source, "    ];")?;
+    writeln!(source, "}}")?;
+
+    writeln!(source, "pub mod fonts {{")?;
+    for font in fonts {
+        let ident = const_name(&font.name);
+        let data = write_blob(out_dir, &format!("font_{}.bin", ident), &font.data)?;
+        let style = match font.style {
+            FontStyle::Bitmap => "Bitmap",
+            FontStyle::Antialiased => "Antialiased",
+        };
+
+        writeln!(
+            source,
+            "    pub const {}: crate::assets::Font = crate::assets::Font {{ style: \
+             crate::assets::FontStyle::{}, size: {}, ascent: {}, descent: {}, line_height: {}, \
+             data: {}, glyphs: &[",
+            ident, style, font.size, font.ascent, font.descent, font.line_height, data
+        )?;
+        for glyph in &font.glyphs {
+            writeln!(
+                source,
+                "        crate::assets::Glyph {{ character: {:?}, width: {}, height: {}, \
+                 x_offset: {}, y_offset: {}, advance: {}, offset: {} }},",
+                glyph.character,
+                glyph.width,
+                glyph.height,
+                glyph.x_offset,
+                glyph.y_offset,
+                glyph.advance,
+                glyph.offset
+            )?;
+        }
+        writeln!(source, "    ] }};")?;
+    }
+    writeln!(source, "}}")?;
+
+    Ok(source)
+}
@@ -0,0 +1,298 @@
+use std::{error::Error, fs, path::Path};
+
+use fontdue::{Font, FontSettings};
+
+use super::{
+    codegen::asset_files,
+    images::pack_bits,
+    manifest::{FontOptions, FontStyle, Manifest},
+};
+
+// Size used for outline fonts when the manifest does not list any.
+const DEFAULT_SIZE: u16 = 20;
+
+// Coverage at or above this value becomes a set pixel in bitmap fonts.
+const COVERAGE_THRESHOLD: u8 = 128;
+
+pub struct ConvertedFont {
+    pub name: String,
+    pub style: FontStyle,
+    pub size: u16,
+    pub ascent: i16,
+    pub descent: i16,
+    pub line_height: u16,
+    pub glyphs: Vec<ConvertedGlyph>,
+    pub data: Vec<u8>,
+}
+
+pub struct ConvertedGlyph {
+    pub character: char,
+    pub width: u8,
+    pub height: u8,
+    pub x_offset: i8,
+    pub y_offset: i8,
+    pub advance: u8,
+    pub offset: u32,
+}
+
+// Glyph rasterized to one coverage byte per pixel, before being packed.
+struct RasterGlyph {
+    character: char,
+    width: usize,
+    height: usize,
+    x_offset: i32,
+    y_offset: i32,
+    advance: i32,
+    coverage: Vec<u8>,
+}
+
+pub fn convert_all(dir: &Path, manifest: &Manifest) -> Result<Vec<ConvertedFont>, Box<dyn Error>> {
+    let mut fonts = Vec::new();
+
+    for (name, path) in asset_files(dir, &["ttf", "otf", "bdf"])? {
+        let default_options = FontOptions::default();
+        let options = manifest.font(&name).unwrap_or(&default_options);
+
+        let converted = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bdf") => convert_bdf(&path, name, options).map(|font| vec![font]),
+            _ => convert_outline(&path, name, options),
+        };
+
+        fonts.extend(converted.map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+
+    Ok(fonts)
+}
+
+fn charset(options: &FontOptions) -> Vec<char> {
+    let mut chars: Vec<char> = (' '..='~').chain('\u{A0}'..='\u{FF}').collect();
+
+    if let Some(extra) = &options.chars {
+        chars.extend(extra.chars());
+    }
+
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+fn convert_outline(
+    path: &Path,
+    name: String,
+    options: &FontOptions,
+) -> Result<Vec<ConvertedFont>, Box<dyn Error>> {
+    let font = Font::from_bytes(fs::read(path)?, FontSettings::default())?;
+    let style = options.style.unwrap_or(FontStyle::Antialiased);
+    let sizes = options.sizes.clone().unwrap_or_else(|| vec![DEFAULT_SIZE]);
+    let chars = charset(options);
+
+    sizes
+        .into_iter()
+        .map(|size| {
+            let px = size as f32;
+            let metrics = font
+                .horizontal_line_metrics(px)
+                .ok_or("font has no horizontal metrics")?;
+
+            let glyphs = chars
+                .iter()
+                .filter(|&&character| font.has_glyph(character))
+                .map(|&character| {
+                    let (glyph, coverage) = font.rasterize(character, px);
+
+                    RasterGlyph {
+                        character,
+                        width: glyph.width,
+                        height: glyph.height,
+                        x_offset: glyph.xmin,
+                        y_offset: -(glyph.ymin + glyph.height as i32),
+                        advance: glyph.advance_width.round() as i32,
+                        coverage,
+                    }
+                })
+                .collect();
+
+            pack(
+                format!("{}_{}", name, size),
+                style,
+                size,
+                metrics.ascent.round() as i32,
+                metrics.descent.round() as i32,
+                metrics.new_line_size.round() as i32,
+                glyphs,
+            )
+        })
+        .collect()
+}
+
+fn convert_bdf(
+    path: &Path,
+    name: String,
+    options: &FontOptions,
+) -> Result<ConvertedFont, Box<dyn Error>> {
+    if options.sizes.is_some() || options.style == Some(FontStyle::Antialiased) {
+        return Err("BDF fonts are bitmap fonts and cannot be resized or anti-aliased".into());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let chars = charset(options);
+
+    let mut size = 0;
+    let mut ascent = 0;
+    let mut descent = 0;
+    let mut glyphs = Vec::new();
+
+    let mut lines = content.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let mut fields = line.split_whitespace();
+
+        match fields.next() {
+            Some("SIZE") => size = parse_field(&mut fields)?,
+            Some("FONT_ASCENT") => ascent = parse_field(&mut fields)?,
+            Some("FONT_DESCENT") => descent = -parse_field::<i32>(&mut fields)?,
+            Some("STARTCHAR") => {
+                if let Some(glyph) = parse_bdf_glyph(&mut lines)?
+                    && chars.binary_search(&glyph.character).is_ok()
+                {
+                    glyphs.push(glyph);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    glyphs.sort_by_key(|glyph| glyph.character);
+
+    pack(
+        name,
+        FontStyle::Bitmap,
+        size,
+        ascent,
+        descent,
+        ascent - descent,
+        glyphs,
+    )
+}
+
+fn parse_bdf_glyph<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<Option<RasterGlyph>, Box<dyn Error>> {
+    let mut character = None;
+    let mut advance = 0;
+    let mut bbx = (0usize, 0usize, 0i32, 0i32);
+
+    for line in lines.by_ref() {
+        let mut fields = line.split_whitespace();
+
+        match fields.next() {
+            Some("ENCODING") => {
+                let code: i64 = parse_field(&mut fields)?;
+                character = u32::try_from(code).ok().and_then(char::from_u32);
+            },
+            Some("DWIDTH") => advance = parse_field(&mut fields)?,
+            Some("BBX") => {
+                bbx = (
+                    parse_field(&mut fields)?,
+                    parse_field(&mut fields)?,
+                    parse_field(&mut fields)?,
+                    parse_field(&mut fields)?,
+                );
+            },
+            Some("BITMAP") => break,
+            _ => {},
+        }
+    }
+
+    let (width, height, x_offset, y_bottom) = bbx;
+    let row_bytes = width.div_ceil(8);
+    let mut coverage = Vec::with_capacity(width * height);
+
+    for line in lines.by_ref().take(height) {
+        let row = (0..row_bytes)
+            .map(|i| u8::from_str_radix(line.get(i * 2..i * 2 + 2).unwrap_or("00"), 16))
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        coverage.extend((0..width).map(|x| {
+            if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                u8::MAX
+            } else {
+                0
+            }
+        }));
+    }
+
+    if lines.next() != Some("ENDCHAR") {
+        return Err("malformed glyph bitmap".into());
+    }
+
+    Ok(character.map(|character| RasterGlyph {
+        character,
+        width,
+        height,
+        x_offset,
+        y_offset: -(y_bottom + height as i32),
+        advance,
+        coverage,
+    }))
+}
+
+fn parse_field<'a, T>(fields: &mut impl Iterator<Item = &'a str>) -> Result<T, Box<dyn Error>>
+where
+    T: std::str::FromStr,
+    T::Err: Error + 'static,
+{
+    Ok(fields.next().ok_or("missing field")?.parse()?)
+}
+
+fn pack(
+    name: String,
+    style: FontStyle,
+    size: u16,
+    ascent: i32,
+    descent: i32,
+    line_height: i32,
+    raster: Vec<RasterGlyph>,
+) -> Result<ConvertedFont, Box<dyn Error>> {
+    let mut glyphs = Vec::with_capacity(raster.len());
+    let mut data = Vec::new();
+
+    for glyph in raster {
+        let out_of_range = || format!("glyph {:?} is too large", glyph.character);
+
+        glyphs.push(ConvertedGlyph {
+            character: glyph.character,
+            width: u8::try_from(glyph.width).map_err(|_| out_of_range())?,
+            height: u8::try_from(glyph.height).map_err(|_| out_of_range())?,
+            x_offset: i8::try_from(glyph.x_offset).map_err(|_| out_of_range())?,
+            y_offset: i8::try_from(glyph.y_offset).map_err(|_| out_of_range())?,
+            advance: u8::try_from(glyph.advance).map_err(|_| out_of_range())?,
+            offset: data.len() as u32,
+        });
+
+        match style {
+            FontStyle::Bitmap => data.extend(pack_bits(
+                glyph
+                    .coverage
+                    .iter()
+                    .map(|&coverage| coverage >= COVERAGE_THRESHOLD),
+            )),
+            FontStyle::Antialiased => data.extend(
+                glyph
+                    .coverage
+                    .chunks(2)
+                    .map(|pair| (pair[0] >> 4) << 4 | pair.get(1).map_or(0, |c| c >> 4)),
+            ),
+        }
+    }
+
+    Ok(ConvertedFont {
+        name,
+        style,
+        size,
+        ascent: ascent as i16,
+        descent: descent as i16,
+        line_height: line_height as u16,
+        glyphs,
+        data,
+    })
+}
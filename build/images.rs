@@ -0,0 +1,136 @@
+use std::{error::Error, fs::File, io::BufReader, path::Path};
+
+use png::{ColorType, Decoder, Transformations};
+
+use super::{
+    codegen::asset_files,
+    manifest::{ImageFormat, Manifest},
+};
+
+// Pixels with an alpha value below this threshold are considered transparent.
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Longest run a single RLE entry can encode.
+const MAX_RUN: usize = u8::MAX as usize;
+
+pub struct ConvertedImage {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    pub format: ImageFormat,
+    pub data: Vec<u8>,
+    pub mask: Option<Vec<u8>>,
+}
+
+pub fn convert_all(dir: &Path, manifest: &Manifest) -> Result<Vec<ConvertedImage>, Box<dyn Error>> {
+    asset_files(dir, &["png"])?
+        .into_iter()
+        .map(|(name, path)| {
+            let options = manifest.image(&name);
+            convert(&path, name, options.format)
+                .map_err(|e| format!("{}: {}", path.display(), e).into())
+        })
+        .collect()
+}
+
+fn convert(
+    path: &Path,
+    name: String,
+    format: ImageFormat,
+) -> Result<ConvertedImage, Box<dyn Error>> {
+    let mut decoder = Decoder::new(BufReader::new(File::open(path)?));
+    decoder.set_transformations(Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+
+    let width = u16::try_from(info.width).map_err(|_| "image is too wide")?;
+    let height = u16::try_from(info.height).map_err(|_| "image is too tall")?;
+
+    let pixels: Vec<(u16, bool)> = buffer[..info.buffer_size()]
+        .chunks_exact(info.color_type.samples())
+        .map(|pixel| match info.color_type {
+            ColorType::Grayscale => (rgb565(pixel[0], pixel[0], pixel[0]), true),
+            ColorType::GrayscaleAlpha => (
+                rgb565(pixel[0], pixel[0], pixel[0]),
+                pixel[1] >= ALPHA_THRESHOLD,
+            ),
+            ColorType::Rgb => (rgb565(pixel[0], pixel[1], pixel[2]), true),
+            ColorType::Rgba => (
+                rgb565(pixel[0], pixel[1], pixel[2]),
+                pixel[3] >= ALPHA_THRESHOLD,
+            ),
+            ColorType::Indexed => unreachable!("indexed images are expanded by the decoder"),
+        })
+        .collect();
+
+    let mask = pixels
+        .iter()
+        .any(|&(_, opaque)| !opaque)
+        .then(|| pack_bits(pixels.iter().map(|&(_, opaque)| opaque)));
+
+    let colors = pixels.iter().map(|&(color, _)| color);
+    let data = match format {
+        ImageFormat::Rgb565 => colors.flat_map(u16::to_be_bytes).collect(),
+        ImageFormat::Rle => encode_rle(colors),
+    };
+
+    Ok(ConvertedImage {
+        name,
+        width,
+        height,
+        format,
+        data,
+        mask,
+    })
+}
+
+fn rgb565(r: u8, g: u8, b: u8) -> u16 {
+    ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3)
+}
+
+fn encode_rle(colors: impl Iterator<Item = u16>) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut run: Option<(u16, usize)> = None;
+
+    for color in colors {
+        run = match run {
+            Some((current, length)) if current == color && length < MAX_RUN => {
+                Some((current, length + 1))
+            },
+            Some((current, length)) => {
+                push_run(&mut data, current, length);
+                Some((color, 1))
+            },
+            None => Some((color, 1)),
+        };
+    }
+
+    if let Some((current, length)) = run {
+        push_run(&mut data, current, length);
+    }
+
+    data
+}
+
+fn push_run(data: &mut Vec<u8>, color: u16, length: usize) {
+    data.push(length as u8);
+    data.extend_from_slice(&color.to_be_bytes());
+}
+
+pub fn pack_bits(bits: impl Iterator<Item = bool>) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for (i, bit) in bits.enumerate() {
+        if i.is_multiple_of(8) {
+            data.push(0);
+        }
+
+        if bit {
+            *data.last_mut().unwrap() |= 0x80 >> (i % 8);
+        }
+    }
+
+    data
+}
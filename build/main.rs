@@ -0,0 +1,32 @@
+mod codegen;
+mod fonts;
+mod images;
+mod manifest;
+
+use std::{env, error::Error, fs, path::PathBuf};
+
+use self::manifest::Manifest;
+
+const ASSETS_DIR: &str = "assets";
+const MANIFEST_FILE: &str = "assets.toml";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Host builds, used for unit tests, link without the chip's linker script.
+    if env::var("CARGO_CFG_TARGET_OS")? == "none" {
+        println!("cargo:rustc-link-arg=-Tlinkall.x");
+    }
+    println!("cargo:rerun-if-changed={}", ASSETS_DIR);
+
+    let assets_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?).join(ASSETS_DIR);
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+    let manifest = Manifest::load(&assets_dir.join(MANIFEST_FILE))?;
+
+    let images = images::convert_all(&assets_dir.join("images"), &manifest)?;
+    let fonts = fonts::convert_all(&assets_dir.join("fonts"), &manifest)?;
+
+    let source = codegen::generate(&out_dir, &images, &fonts)?;
+    fs::write(out_dir.join("assets.rs"), source)?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default)]
+    pub images: HashMap<String, ImageOptions>,
+    #[serde(default)]
+    pub fonts: HashMap<String, FontOptions>,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImageOptions {
+    #[serde(default)]
+    pub format: ImageFormat,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    #[default]
+    Rgb565,
+    Rle,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FontOptions {
+    pub sizes: Option<Vec<u16>>,
+    pub style: Option<FontStyle>,
+    pub chars: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FontStyle {
+    Bitmap,
+    Antialiased,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    pub fn image(&self, name: &str) -> ImageOptions {
+        self.images.get(name).cloned().unwrap_or_default()
+    }
+
+    pub fn font(&self, name: &str) -> Option<&FontOptions> {
+        self.fonts.get(name)
+    }
+}
@@ -1,26 +1,76 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, rc::Rc, string::String};
 
-use embassy_time::{Duration, Timer};
-use log::{debug, error, info};
+use embassy_futures::select::{Either, select};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::{Duration, Instant, Timer};
+use log::{debug, error, info, warn};
 
 use crate::{
+    clock::{SystemClock, WallTime},
+    config::{self, ViewConfig},
+    entity::{EntityState, EntityStore},
     error::SmartknobError,
     hardware::Hardware,
-    peripherals::display::{Display, graphics::Color},
-    ui::{LightView, View, ViewManager},
+    peripherals::display::Display,
+    ui::{
+        ClockView, DEFAULT_TRANSITION_DURATION, DEFAULT_TRANSITION_EASING, InputEvent, Navigation,
+        Toast, Transition, View, ViewManager, animation,
+    },
 };
 
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const EVENT_QUEUE_LEN: usize = 8;
+// Until the first input event arrives, the views are cycled through on their own.
+const VIEW_INTERVAL: Duration = Duration::from_millis(1000);
+
+// Something the app reacts to, sent from the tasks reading input or talking to the entities.
+pub enum Event {
+    Input(InputEvent),
+    // Shows a message over the current view, such as the outcome of a command.
+    Toast(Toast),
+    // State reported by an entity itself. Attributes left `None` keep their last known value.
+    Entity { id: String, state: EntityState },
+    // Time of day synced from the server, shown by the screensaver.
+    Time(WallTime),
+}
+
+// Events waiting to be handled by `App::run`.
+pub static EVENTS: Channel<CriticalSectionRawMutex, Event, EVENT_QUEUE_LEN> = Channel::new();
+
 pub struct App {
     display: Display,
     view: ViewManager,
+    clock: Rc<SystemClock>,
+    entities: EntityStore,
+    // Whether any input event arrived, which stops the views cycling on their own.
+    has_input: bool,
 }
 
 impl App {
+    // Starts with the views stored in flash, or the default ones if none are stored or they
+    // cannot be read.
     pub async fn new() -> Result<Self, SmartknobError> {
-        info!("Starting application");
+        let mut hardware = Hardware::init().await?;
 
+        let config = match hardware.storage.read_config() {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to read stored configuration: {}", e);
+                None
+            },
+        };
+
+        Self::with_hardware(hardware, config.as_deref().unwrap_or(config::DEFAULT))
+    }
+
+    // Starts with the views described by `config`, or the default ones if it is invalid.
+    pub async fn with_config(config: &[u8]) -> Result<Self, SmartknobError> {
         let hardware = Hardware::init().await?;
-        debug!("Components initialized successfully");
+        Self::with_hardware(hardware, config)
+    }
+
+    fn with_hardware(hardware: Hardware, config: &[u8]) -> Result<Self, SmartknobError> {
+        info!("Starting application");
 
         let display = Display::new(
             hardware.display_spi,
@@ -30,10 +80,26 @@ impl App {
         debug!("Display interface created successfully");
 
         let mut view = ViewManager::new();
-        view.add(Box::new(LightView::new("Luz Dormitorio")));
-        view.add(Box::new(LightView::new("Luz Salon")));
+        ViewConfig::load(config).build(&mut view);
+        view.set_transition(
+            Transition::SlideLeft,
+            DEFAULT_TRANSITION_DURATION,
+            DEFAULT_TRANSITION_EASING,
+        );
+
+        let clock = Rc::new(SystemClock::new());
+        view.set_screensaver(
+            Box::new(ClockView::new("Reloj").with_source(clock.clone())),
+            IDLE_TIMEOUT,
+        );
 
-        Ok(Self { display, view })
+        Ok(Self {
+            display,
+            view,
+            clock,
+            entities: EntityStore::new(),
+            has_input: false,
+        })
     }
 
     pub async fn run(&mut self) -> Result<(), SmartknobError> {
@@ -45,24 +111,82 @@ impl App {
             },
         }
 
-        const BLACK: Color = Color::BLACK;
-
-        self.display.clear(BLACK);
-        self.view.select(0, &mut self.display);
-        self.display.render().await?;
-
-        let mut index = 0;
+        let mut last_tick = Instant::now();
+        let mut shown = Instant::now();
 
         info!("Starting main loop");
         loop {
-            self.display.clear(BLACK);
-            self.view.select(index, &mut self.display);
-            self.display.render().await?;
-            index += 1;
-            if index > self.view.len() {
-                index = 0;
+            let now = Instant::now();
+            self.view.tick(now.duration_since(last_tick));
+            last_tick = now;
+
+            // The screensaver is left up, or cycling would dismiss it as soon as it shows.
+            if !self.has_input
+                && !self.view.is_screensaver_shown()
+                && shown.elapsed() >= VIEW_INTERVAL
+            {
+                self.view.navigate(Navigation::Next);
+                shown = Instant::now();
             }
-            Timer::after(Duration::from_millis(1000)).await;
+
+            self.update().await?;
+
+            // Events are handled as soon as they arrive, otherwise the next frame is drawn when
+            // it is due.
+            let interval = animation::frame_interval(self.view.is_animating());
+            if let Either::First(event) = select(EVENTS.receive(), Timer::after(interval)).await {
+                self.handle(event);
+            }
+        }
+    }
+
+    pub fn entities(&self) -> &EntityStore {
+        &self.entities
+    }
+
+    // Applies an event to the views, which are redrawn on the next pass of the main loop.
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Input(input) => {
+                self.has_input = true;
+                self.handle_input(input);
+            },
+            Event::Toast(toast) => self.view.show_toast(toast),
+            Event::Entity { id, state } => {
+                if self.entities.update(&id, |current| current.merge(state)) {
+                    self.notify_changes();
+                }
+            },
+            Event::Time(time) => self.clock.sync(time),
+        }
+    }
+
+    // Feeds an input event to the focused view.
+    fn handle_input(&mut self, event: InputEvent) {
+        if let Some(command) = self.view.dispatch(event) {
+            info!("Command {:?} for {}", command.action, command.target);
+            self.entities.apply(&command);
+
+            // The view that sent the command already shows the new state.
+            for entity in self.entities.take_changed() {
+                self.view.notify_others(entity);
+            }
+        }
+    }
+
+    fn notify_changes(&mut self) {
+        for entity in self.entities.take_changed() {
+            self.view.notify(entity);
         }
     }
+
+    // Draws the current view and flushes the areas that changed to the panel.
+    async fn update(&mut self) -> Result<(), SmartknobError> {
+        let regions = self.view.update(&mut self.display)?;
+        if !regions.is_empty() {
+            self.display.render_regions(&regions).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,75 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FontStyle {
+    // One bit per pixel.
+    Bitmap,
+    // Four bits of coverage per pixel.
+    Antialiased,
+}
+
+pub struct Glyph {
+    pub character: char,
+    pub width: u8,
+    pub height: u8,
+    pub x_offset: i8,
+    // Offset of the top row relative to the baseline, negative above it.
+    pub y_offset: i8,
+    pub advance: u8,
+    pub offset: u32,
+}
+
+pub struct Font {
+    pub style: FontStyle,
+    pub size: u16,
+    pub ascent: i16,
+    pub descent: i16,
+    pub line_height: u16,
+    // Sorted by character.
+    pub glyphs: &'static [Glyph],
+    pub data: &'static [u8],
+}
+
+impl Font {
+    pub fn glyph(&self, character: char) -> Option<&Glyph> {
+        self.glyphs
+            .binary_search_by_key(&character, |glyph| glyph.character)
+            .ok()
+            .map(|index| &self.glyphs[index])
+    }
+
+    // Coverage of a glyph pixel, from 0 (background) to 255 (foreground).
+    pub fn coverage(&self, glyph: &Glyph, x: u8, y: u8) -> u8 {
+        if x >= glyph.width || y >= glyph.height {
+            return 0;
+        }
+
+        let index = (y as usize) * (glyph.width as usize) + (x as usize);
+        let offset = glyph.offset as usize;
+
+        match self.style {
+            FontStyle::Bitmap => {
+                let byte = self.data[offset + index / 8];
+                if byte & (0x80 >> (index % 8)) != 0 {
+                    u8::MAX
+                } else {
+                    0
+                }
+            },
+            FontStyle::Antialiased => {
+                let byte = self.data[offset + index / 2];
+                let nibble = if index.is_multiple_of(2) {
+                    byte >> 4
+                } else {
+                    byte & 0x0F
+                };
+                nibble * 0x11
+            },
+        }
+    }
+
+    pub fn text_width(&self, text: &str) -> u32 {
+        text.chars()
+            .filter_map(|character| self.glyph(character))
+            .map(|glyph| glyph.advance as u32)
+            .sum()
+    }
+}
@@ -0,0 +1,107 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    // Two big-endian bytes per pixel, row by row.
+    Rgb565,
+    // Runs of `[length, color_hi, color_lo]`.
+    Rle,
+}
+
+pub struct Image {
+    pub width: u16,
+    pub height: u16,
+    pub format: ImageFormat,
+    pub data: &'static [u8],
+    // One bit per pixel, set when the pixel is opaque. `None` when the whole image is opaque.
+    pub mask: Option<&'static [u8]>,
+}
+
+impl Image {
+    pub fn len(&self) -> usize {
+        (self.width as usize) * (self.height as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_opaque(&self, x: u16, y: u16) -> bool {
+        let index = (y as usize) * (self.width as usize) + (x as usize);
+
+        self.mask
+            .is_none_or(|mask| mask[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+
+    // Random access to a single pixel. RLE images are decoded from the start, so prefer
+    // `pixels()` when walking the whole image.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<u16> {
+        if x >= self.width || y >= self.height || !self.is_opaque(x, y) {
+            return None;
+        }
+
+        let index = (y as usize) * (self.width as usize) + (x as usize);
+
+        match self.format {
+            ImageFormat::Rgb565 => Some(u16::from_be_bytes([
+                self.data[index * 2],
+                self.data[index * 2 + 1],
+            ])),
+            ImageFormat::Rle => self.pixels().nth(index).flatten(),
+        }
+    }
+
+    // Iterates the pixels row by row, yielding `None` for transparent ones.
+    pub fn pixels(&self) -> Pixels<'_> {
+        Pixels {
+            image: self,
+            index: 0,
+            offset: 0,
+            run: 0,
+        }
+    }
+}
+
+pub struct Pixels<'a> {
+    image: &'a Image,
+    index: usize,
+    offset: usize,
+    run: u8,
+}
+
+impl Iterator for Pixels<'_> {
+    type Item = Option<u16>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.image.len() {
+            return None;
+        }
+
+        let data = self.image.data;
+        let color = match self.image.format {
+            ImageFormat::Rgb565 => {
+                let color = u16::from_be_bytes([data[self.offset], data[self.offset + 1]]);
+                self.offset += 2;
+                color
+            },
+            ImageFormat::Rle => {
+                if self.run == data[self.offset] {
+                    self.offset += 3;
+                    self.run = 0;
+                }
+
+                self.run += 1;
+                u16::from_be_bytes([data[self.offset + 1], data[self.offset + 2]])
+            },
+        };
+
+        let x = (self.index % self.image.width as usize) as u16;
+        let y = (self.index / self.image.width as usize) as u16;
+        self.index += 1;
+
+        Some(self.image.is_opaque(x, y).then_some(color))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.image.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
@@ -0,0 +1,18 @@
+mod font;
+mod image;
+
+pub use self::{
+    font::{Font, FontStyle, Glyph},
+    image::{Image, ImageFormat, Pixels},
+};
+
+// Generated by the build script from the contents of the `assets/` directory.
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+// Image converted from `images/<name>.png`, for assets chosen at runtime.
+pub fn image(name: &str) -> Option<&'static Image> {
+    images::ALL
+        .iter()
+        .find(|(image, _)| *image == name)
+        .map(|(_, image)| *image)
+}
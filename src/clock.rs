@@ -0,0 +1,79 @@
+use core::cell::Cell;
+
+use embassy_time::Instant;
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+// Time of day, in seconds since midnight.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WallTime(u32);
+
+impl WallTime {
+    pub fn new(hours: u8, minutes: u8, seconds: u8) -> Self {
+        Self::from_seconds(hours as u32 * 3600 + minutes as u32 * 60 + seconds as u32)
+    }
+
+    // Wraps around midnight.
+    pub fn from_seconds(seconds: u32) -> Self {
+        Self(seconds % SECONDS_PER_DAY)
+    }
+
+    pub fn seconds_of_day(&self) -> u32 {
+        self.0
+    }
+
+    pub fn hours(&self) -> u8 {
+        (self.0 / 3600) as u8
+    }
+
+    pub fn minutes(&self) -> u8 {
+        (self.0 / 60 % 60) as u8
+    }
+
+    pub fn seconds(&self) -> u8 {
+        (self.0 % 60) as u8
+    }
+}
+
+pub trait TimeSource {
+    // Current time of day, or `None` if it is not known yet.
+    fn now(&self) -> Option<WallTime>;
+}
+
+// Time of day kept by counting from a reference point, which is set by hand or synced from an
+// external source such as the home automation server.
+#[derive(Default)]
+pub struct SystemClock {
+    reference: Cell<Option<(WallTime, Instant)>>,
+    synced: Cell<bool>,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, time: WallTime) {
+        self.reference.set(Some((time, Instant::now())));
+        self.synced.set(false);
+    }
+
+    pub fn sync(&self, time: WallTime) {
+        self.reference.set(Some((time, Instant::now())));
+        self.synced.set(true);
+    }
+
+    // Whether the time came from an external source rather than being set by hand.
+    pub fn is_synced(&self) -> bool {
+        self.synced.get()
+    }
+}
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> Option<WallTime> {
+        let (time, at) = self.reference.get()?;
+        let elapsed = (at.elapsed().as_secs() % SECONDS_PER_DAY as u64) as u32;
+
+        Some(WallTime::from_seconds(time.seconds_of_day() + elapsed))
+    }
+}
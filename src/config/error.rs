@@ -0,0 +1,37 @@
+use core::fmt;
+
+// Problem found in a view configuration, with the line it is on, counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    InvalidEncoding,
+    Empty,
+    Syntax { line: usize, message: &'static str },
+    UnknownKind { line: usize },
+    UnknownKey { line: usize },
+    DuplicateKey { line: usize, key: &'static str },
+    MissingKey { line: usize, key: &'static str },
+    InvalidValue { line: usize, key: &'static str },
+    UnknownIcon { line: usize },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEncoding => write!(f, "Configuration is not valid UTF-8"),
+            Self::Empty => write!(f, "Configuration has no views"),
+            Self::Syntax { line, message } => {
+                write!(f, "Syntax error on line {}: {}", line, message)
+            },
+            Self::UnknownKind { line } => write!(f, "Unknown view kind on line {}", line),
+            Self::UnknownKey { line } => write!(f, "Unknown key on line {}", line),
+            Self::DuplicateKey { line, key } => {
+                write!(f, "Key `{}` repeated on line {}", key, line)
+            },
+            Self::MissingKey { line, key } => write!(f, "Missing `{}` on line {}", key, line),
+            Self::InvalidValue { line, key } => {
+                write!(f, "Invalid value for `{}` on line {}", key, line)
+            },
+            Self::UnknownIcon { line } => write!(f, "Unknown icon on line {}", line),
+        }
+    }
+}
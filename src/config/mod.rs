@@ -0,0 +1,608 @@
+pub mod error;
+mod parser;
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str;
+
+use log::{debug, error};
+
+use self::{
+    error::ConfigError,
+    parser::{Token, fixed, tokens},
+};
+use crate::{
+    assets::{self, Image},
+    entity::Domain,
+    ui::{
+        ColorView, LightView, MediaView, MenuItem, MenuView, NumberView, ThermostatView,
+        ValuePicker, View, ViewManager,
+    },
+};
+
+// Configuration used when none is stored in flash or the stored one is invalid. See it for the
+// format.
+pub const DEFAULT: &[u8] = include_bytes!("default.conf");
+
+const KEYS: [&str; 8] = [
+    "entity",
+    "icon",
+    "temperature",
+    "min",
+    "max",
+    "step",
+    "decimals",
+    "unit",
+];
+
+// Thermostat limits are given in degrees and kept in tenths of a degree.
+const THERMOSTAT_DECIMALS: u8 = 1;
+const MAX_DECIMALS: u8 = 3;
+const MIN_KELVIN: u16 = 1000;
+const MAX_KELVIN: u16 = 12000;
+
+// Default range of number views, in whole units, scaled by the decimals the view is given.
+const DEFAULT_NUMBER_MIN: i32 = 0;
+const DEFAULT_NUMBER_MAX: i32 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewKind {
+    Menu,
+    Light,
+    Thermostat,
+    Media,
+    Number,
+    Color,
+}
+
+impl ViewKind {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "menu" => Some(Self::Menu),
+            "light" => Some(Self::Light),
+            "thermostat" => Some(Self::Thermostat),
+            "media" => Some(Self::Media),
+            "number" => Some(Self::Number),
+            "color" => Some(Self::Color),
+            _ => None,
+        }
+    }
+
+    // Domain the entity of a view must be in, as commands are applied to the entity according to
+    // it.
+    fn domain(self) -> Option<Domain> {
+        match self {
+            Self::Menu => None,
+            Self::Light | Self::Color => Some(Domain::Light),
+            Self::Thermostat => Some(Domain::Climate),
+            Self::Media => Some(Domain::MediaPlayer),
+            Self::Number => Some(Domain::Number),
+        }
+    }
+
+    fn accepts(self, key: &str) -> bool {
+        match key {
+            "icon" => true,
+            "entity" => self != Self::Menu,
+            "temperature" => self == Self::Light,
+            "min" | "max" => matches!(self, Self::Thermostat | Self::Number),
+            "step" | "decimals" | "unit" => self == Self::Number,
+            _ => false,
+        }
+    }
+}
+
+// One view of the configuration. Limits are integers in the units the view keeps them in.
+pub struct ViewEntry {
+    pub kind: ViewKind,
+    pub title: String,
+    pub entity: Option<String>,
+    pub icon: Option<&'static Image>,
+    pub temperature: Option<u16>,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+    pub step: Option<i32>,
+    pub decimals: u8,
+    pub unit: Option<String>,
+}
+
+impl ViewEntry {
+    fn parse(line: &str, number: usize) -> Result<Self, ConfigError> {
+        let tokens = tokens(line, number)?;
+        let mut tokens = tokens.into_iter();
+
+        let kind = match tokens.next() {
+            Some(Token::Word(kind)) => {
+                ViewKind::parse(kind).ok_or(ConfigError::UnknownKind { line: number })?
+            },
+            _ => {
+                return Err(ConfigError::Syntax {
+                    line: number,
+                    message: "expected a view kind",
+                });
+            },
+        };
+
+        let title = match tokens.next() {
+            Some(Token::Word(title)) => title.to_string(),
+            _ => {
+                return Err(ConfigError::Syntax {
+                    line: number,
+                    message: "expected a title",
+                });
+            },
+        };
+
+        let mut pairs: Vec<(&'static str, &str)> = Vec::new();
+        for token in tokens {
+            let Token::Pair(key, value) = token else {
+                return Err(ConfigError::Syntax {
+                    line: number,
+                    message: "expected `key=value`",
+                });
+            };
+
+            let key = KEYS
+                .into_iter()
+                .find(|known| *known == key && kind.accepts(key))
+                .ok_or(ConfigError::UnknownKey { line: number })?;
+
+            if pairs.iter().any(|(seen, _)| *seen == key) {
+                return Err(ConfigError::DuplicateKey { line: number, key });
+            }
+
+            pairs.push((key, value));
+        }
+
+        let get = |key: &str| {
+            pairs
+                .iter()
+                .find(|(seen, _)| *seen == key)
+                .map(|(_, value)| *value)
+        };
+        let invalid = |key| ConfigError::InvalidValue { line: number, key };
+
+        let decimals = match (kind, get("decimals")) {
+            (ViewKind::Thermostat, _) => THERMOSTAT_DECIMALS,
+            (_, Some(value)) => value
+                .parse()
+                .ok()
+                .filter(|decimals| *decimals <= MAX_DECIMALS)
+                .ok_or(invalid("decimals"))?,
+            (_, None) => 0,
+        };
+
+        let limit = |key| {
+            get(key)
+                .map(|value| fixed(value, decimals).ok_or(invalid(key)))
+                .transpose()
+        };
+
+        let entry = Self {
+            kind,
+            title,
+            entity: get("entity").map(ToString::to_string),
+            icon: get("icon")
+                .map(|name| assets::image(name).ok_or(ConfigError::UnknownIcon { line: number }))
+                .transpose()?,
+            temperature: get("temperature")
+                .map(|value| {
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|kelvin| (MIN_KELVIN..=MAX_KELVIN).contains(kelvin))
+                        .ok_or(invalid("temperature"))
+                })
+                .transpose()?,
+            min: limit("min")?,
+            max: limit("max")?,
+            step: limit("step")?,
+            decimals,
+            unit: get("unit").map(ToString::to_string),
+        };
+
+        entry.validate(number)?;
+        Ok(entry)
+    }
+
+    fn validate(&self, number: usize) -> Result<(), ConfigError> {
+        if let Some(domain) = self.kind.domain() {
+            let Some(entity) = &self.entity else {
+                return Err(ConfigError::MissingKey {
+                    line: number,
+                    key: "entity",
+                });
+            };
+
+            if Domain::of(entity) != domain {
+                return Err(ConfigError::InvalidValue {
+                    line: number,
+                    key: "entity",
+                });
+            }
+        }
+
+        // Thermostats have no sensible default range, so it is given in full or not at all.
+        if self.kind == ViewKind::Thermostat {
+            match (self.min, self.max) {
+                (Some(_), None) => {
+                    return Err(ConfigError::MissingKey {
+                        line: number,
+                        key: "max",
+                    });
+                },
+                (None, Some(_)) => {
+                    return Err(ConfigError::MissingKey {
+                        line: number,
+                        key: "min",
+                    });
+                },
+                _ => {},
+            }
+        }
+
+        let (min, max) = self.range();
+        if min >= max {
+            return Err(ConfigError::InvalidValue {
+                line: number,
+                key: "max",
+            });
+        }
+
+        if self.step.is_some_and(|step| step <= 0) {
+            return Err(ConfigError::InvalidValue {
+                line: number,
+                key: "step",
+            });
+        }
+
+        Ok(())
+    }
+
+    fn range(&self) -> (i32, i32) {
+        let scale = 10_i32.pow(self.decimals as u32);
+
+        (
+            self.min.unwrap_or(DEFAULT_NUMBER_MIN * scale),
+            self.max.unwrap_or(DEFAULT_NUMBER_MAX * scale),
+        )
+    }
+
+    // Builds the view for any kind but menus, which list the other views.
+    fn view(&self) -> Option<Box<dyn View>> {
+        let entity = self.entity.as_deref().unwrap_or(&self.title);
+
+        let view: Box<dyn View> = match self.kind {
+            ViewKind::Menu => return None,
+            ViewKind::Light => {
+                let view = LightView::new(&self.title).with_entity(entity);
+
+                match self.temperature {
+                    Some(kelvin) => Box::new(view.with_color_temperature(kelvin)),
+                    None => Box::new(view),
+                }
+            },
+            ViewKind::Thermostat => {
+                let view = ThermostatView::new(&self.title).with_entity(entity);
+
+                match (self.min, self.max) {
+                    (Some(min), Some(max)) => Box::new(view.with_limits(min, max)),
+                    _ => Box::new(view),
+                }
+            },
+            ViewKind::Media => Box::new(MediaView::new(&self.title).with_entity(entity)),
+            ViewKind::Number => {
+                let (min, max) = self.range();
+                let picker = ValuePicker::new(min, max, self.step.unwrap_or(1))
+                    .with_decimals(self.decimals)
+                    .with_unit(self.unit.as_deref().unwrap_or_default())
+                    .with_value(min);
+
+                Box::new(
+                    NumberView::new(&self.title)
+                        .with_entity(entity)
+                        .with_picker(picker),
+                )
+            },
+            ViewKind::Color => Box::new(ColorView::new(&self.title).with_entity(entity)),
+        };
+
+        Some(view)
+    }
+}
+
+// Root views of the knob, in order, read from a line-based text format so the room layout can
+// change without reflashing the firmware. `DEFAULT` documents the format.
+pub struct ViewConfig {
+    pub views: Vec<ViewEntry>,
+}
+
+impl Default for ViewConfig {
+    fn default() -> Self {
+        Self::parse(DEFAULT).expect("default view configuration is valid")
+    }
+}
+
+impl ViewConfig {
+    pub fn parse(data: &[u8]) -> Result<Self, ConfigError> {
+        let text = str::from_utf8(data).map_err(|_| ConfigError::InvalidEncoding)?;
+
+        let mut views = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            views.push(ViewEntry::parse(line, index + 1)?);
+        }
+
+        if views.is_empty() {
+            return Err(ConfigError::Empty);
+        }
+
+        debug!("Parsed configuration with {} views", views.len());
+
+        Ok(Self { views })
+    }
+
+    // Parses `data`, falling back to the default configuration if it is invalid.
+    pub fn load(data: &[u8]) -> Self {
+        match Self::parse(data) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid view configuration, using the default: {}", e);
+                Self::default()
+            },
+        }
+    }
+
+    // Adds the configured views to `manager`, after any it already has.
+    pub fn build(&self, manager: &mut ViewManager) {
+        let first = manager.len();
+
+        for entry in &self.views {
+            let view = match entry.view() {
+                Some(view) => view,
+                None => Box::new(self.menu(entry, first)),
+            };
+
+            manager.add(view);
+        }
+    }
+
+    // Menu listing every view but the menus, each jumping to its root view.
+    fn menu(&self, entry: &ViewEntry, first: usize) -> MenuView {
+        let mut menu = MenuView::new(&entry.title);
+
+        for (index, item) in self.views.iter().enumerate() {
+            if item.kind == ViewKind::Menu {
+                continue;
+            }
+
+            let mut menu_item = MenuItem::navigate(&item.title, first + index);
+            if let Some(icon) = item.icon {
+                menu_item = menu_item.with_icon(icon);
+            }
+
+            menu.add(menu_item);
+        }
+
+        menu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn parse(text: &str) -> Result<ViewConfig, ConfigError> {
+        ViewConfig::parse(text.as_bytes())
+    }
+
+    #[test]
+    fn default_configuration_parses() {
+        assert!(ViewConfig::parse(DEFAULT).is_ok());
+        assert!(!ViewConfig::default().views.is_empty());
+    }
+
+    #[test]
+    fn parses_views() {
+        let config = parse(concat!(
+            "# comment\n",
+            "\n",
+            "menu Inicio\n",
+            "light \"Luz Salon\" entity=light.salon temperature=2700\n",
+            "thermostat Termostato entity=climate.casa min=18.5 max=24\n",
+            "number Persiana entity=cover.salon step=0.5 decimals=1 unit=%\n",
+        ))
+        .unwrap();
+
+        let kinds: Vec<ViewKind> = config.views.iter().map(|view| view.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ViewKind::Menu,
+                ViewKind::Light,
+                ViewKind::Thermostat,
+                ViewKind::Number
+            ]
+        );
+
+        let light = &config.views[1];
+        assert_eq!(light.title, "Luz Salon");
+        assert_eq!(light.entity.as_deref(), Some("light.salon"));
+        assert_eq!(light.temperature, Some(2700));
+
+        let thermostat = &config.views[2];
+        assert_eq!((thermostat.min, thermostat.max), (Some(185), Some(240)));
+
+        let number = &config.views[3];
+        assert_eq!(number.step, Some(5));
+        assert_eq!(number.range(), (0, 1000));
+        assert_eq!(number.unit.as_deref(), Some("%"));
+    }
+
+    #[test]
+    fn rejects_invalid_views() {
+        let cases: [(&str, ConfigError); 16] = [
+            ("", ConfigError::Empty),
+            ("# only comments", ConfigError::Empty),
+            (
+                "fan Ventilador entity=fan.salon",
+                ConfigError::UnknownKind { line: 1 },
+            ),
+            (
+                "light",
+                ConfigError::Syntax {
+                    line: 1,
+                    message: "expected a title",
+                },
+            ),
+            (
+                "light Luz Salon entity=light.salon",
+                ConfigError::Syntax {
+                    line: 1,
+                    message: "expected `key=value`",
+                },
+            ),
+            (
+                "light Luz colour=red entity=light.salon",
+                ConfigError::UnknownKey { line: 1 },
+            ),
+            (
+                "light Luz entity=light.salon min=1",
+                ConfigError::UnknownKey { line: 1 },
+            ),
+            (
+                "light Luz entity=light.a entity=light.b",
+                ConfigError::DuplicateKey {
+                    line: 1,
+                    key: "entity",
+                },
+            ),
+            (
+                "light Luz",
+                ConfigError::MissingKey {
+                    line: 1,
+                    key: "entity",
+                },
+            ),
+            (
+                "thermostat Termostato entity=climate.casa min=18",
+                ConfigError::MissingKey {
+                    line: 1,
+                    key: "max",
+                },
+            ),
+            (
+                "light Luz entity=salon",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "entity",
+                },
+            ),
+            (
+                "thermostat Termostato entity=light.salon",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "entity",
+                },
+            ),
+            (
+                "light Luz entity=light.salon temperature=20000",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "temperature",
+                },
+            ),
+            (
+                "number Valor entity=number.valor min=10 max=5",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "max",
+                },
+            ),
+            (
+                "number Valor entity=number.valor step=0.25 decimals=1",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "step",
+                },
+            ),
+            (
+                "number Valor entity=number.valor decimals=4",
+                ConfigError::InvalidValue {
+                    line: 1,
+                    key: "decimals",
+                },
+            ),
+        ];
+
+        for (text, error) in cases {
+            assert_eq!(parse(text).err(), Some(error), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        let error = parse("menu Inicio\n\n# comment\nlight Luz\n")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            ConfigError::MissingKey {
+                line: 4,
+                key: "entity",
+            }
+        );
+        assert_eq!(error.to_string(), "Missing `entity` on line 4");
+    }
+
+    #[test]
+    fn describes_errors() {
+        let cases = [
+            (
+                ConfigError::InvalidEncoding,
+                "Configuration is not valid UTF-8",
+            ),
+            (ConfigError::Empty, "Configuration has no views"),
+            (
+                ConfigError::Syntax {
+                    line: 2,
+                    message: "unterminated quote",
+                },
+                "Syntax error on line 2: unterminated quote",
+            ),
+            (
+                ConfigError::DuplicateKey {
+                    line: 3,
+                    key: "min",
+                },
+                "Key `min` repeated on line 3",
+            ),
+            (
+                ConfigError::InvalidValue {
+                    line: 5,
+                    key: "decimals",
+                },
+                "Invalid value for `decimals` on line 5",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+        }
+
+        assert_eq!(
+            ViewConfig::parse(b"light \xff").err(),
+            Some(ConfigError::InvalidEncoding)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+
+use super::error::ConfigError;
+
+pub(super) enum Token<'a> {
+    Word(&'a str),
+    Pair(&'a str, &'a str),
+}
+
+// Splits a line into bare or quoted words and `key=value` pairs.
+pub(super) fn tokens(line: &str, number: usize) -> Result<Vec<Token<'_>>, ConfigError> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim();
+
+    while !rest.is_empty() {
+        let (token, remaining) = if rest.starts_with('"') {
+            let (word, remaining) = quoted(rest, number)?;
+            (Token::Word(word), remaining)
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '=')
+                .unwrap_or(rest.len());
+            let (word, after) = rest.split_at(end);
+
+            match after.strip_prefix('=') {
+                Some(value) if value.starts_with('"') => {
+                    let (value, remaining) = quoted(value, number)?;
+                    (Token::Pair(word, value), remaining)
+                },
+                Some(value) => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    (Token::Pair(word, &value[..end]), &value[end..])
+                },
+                None => (Token::Word(word), after),
+            }
+        };
+
+        if !remaining.is_empty() && !remaining.starts_with(char::is_whitespace) {
+            return Err(ConfigError::Syntax {
+                line: number,
+                message: "expected a space after a quoted value",
+            });
+        }
+
+        let empty = match token {
+            Token::Word("") => Some("empty word"),
+            Token::Pair("", _) => Some("empty key"),
+            _ => None,
+        };
+        if let Some(message) = empty {
+            return Err(ConfigError::Syntax {
+                line: number,
+                message,
+            });
+        }
+
+        tokens.push(token);
+        rest = remaining.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+// Splits `"value" rest` into the value, without quotes, and the rest.
+fn quoted(text: &str, number: usize) -> Result<(&str, &str), ConfigError> {
+    let body = &text[1..];
+    let end = body.find('"').ok_or(ConfigError::Syntax {
+        line: number,
+        message: "unterminated quote",
+    })?;
+
+    Ok((&body[..end], &body[end + 1..]))
+}
+
+// Parses a decimal number with up to `decimals` digits after the point into an integer scaled by
+// 10^decimals, so "21.5" with one decimal is 215.
+pub(super) fn fixed(value: &str, decimals: u8) -> Option<i32> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if whole.is_empty() || fraction.len() > decimals as usize {
+        return None;
+    }
+
+    let mut result: i32 = 0;
+    for c in whole.chars().chain(fraction.chars()) {
+        result = result
+            .checked_mul(10)?
+            .checked_add(c.to_digit(10)? as i32)?;
+    }
+
+    for _ in fraction.len()..decimals as usize {
+        result = result.checked_mul(10)?;
+    }
+
+    Some(if negative { -result } else { result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Expected<'a> {
+        Word(&'a str),
+        Pair(&'a str, &'a str),
+    }
+
+    fn parse(line: &str) -> Result<Vec<Expected<'_>>, ConfigError> {
+        let tokens = tokens(line, 1)?;
+
+        Ok(tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Word(word) => Expected::Word(word),
+                Token::Pair(key, value) => Expected::Pair(key, value),
+            })
+            .collect())
+    }
+
+    #[test]
+    fn splits_words_and_pairs() {
+        use Expected::{Pair, Word};
+
+        let cases: [(&str, &[Expected]); 7] = [
+            ("", &[]),
+            ("   ", &[]),
+            ("light Luz", &[Word("light"), Word("Luz")]),
+            ("  light   Luz  ", &[Word("light"), Word("Luz")]),
+            (
+                "light \"Luz Salon\" entity=light.salon",
+                &[
+                    Word("light"),
+                    Word("Luz Salon"),
+                    Pair("entity", "light.salon"),
+                ],
+            ),
+            (
+                "number Valor unit=\"m s\" step=2",
+                &[
+                    Word("number"),
+                    Word("Valor"),
+                    Pair("unit", "m s"),
+                    Pair("step", "2"),
+                ],
+            ),
+            (
+                "menu Inicio icon=",
+                &[Word("menu"), Word("Inicio"), Pair("icon", "")],
+            ),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse(line).unwrap(), expected, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let cases = [
+            ("light \"Luz", "unterminated quote"),
+            ("light unit=\"m", "unterminated quote"),
+            (
+                "light \"Luz\"Salon",
+                "expected a space after a quoted value",
+            ),
+            ("light unit=\"m\"s", "expected a space after a quoted value"),
+            ("light =value", "empty key"),
+            ("light \"\" entity=light.salon", "empty word"),
+        ];
+
+        for (line, message) in cases {
+            assert_eq!(
+                parse(line).err(),
+                Some(ConfigError::Syntax { line: 1, message }),
+                "{:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn parses_fixed_point_numbers() {
+        let cases = [
+            ("21", 0, Some(21)),
+            ("21", 1, Some(210)),
+            ("21.5", 1, Some(215)),
+            ("21.5", 3, Some(21500)),
+            ("-0.25", 2, Some(-25)),
+            ("0.05", 2, Some(5)),
+            ("21.", 1, Some(210)),
+            ("21.5", 0, None),
+            ("0.125", 2, None),
+            (".5", 1, None),
+            ("-", 0, None),
+            ("", 0, None),
+            ("+5", 0, None),
+            ("1.2.3", 3, None),
+            ("2a", 0, None),
+            ("2147483647", 0, Some(i32::MAX)),
+            ("2147483648", 0, None),
+            ("2147483.648", 3, None),
+        ];
+
+        for (value, decimals, expected) in cases {
+            assert_eq!(
+                fixed(value, decimals),
+                expected,
+                "{:?} with {}",
+                value,
+                decimals
+            );
+        }
+    }
+}
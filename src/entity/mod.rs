@@ -0,0 +1,205 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::mem;
+
+use log::debug;
+
+use crate::{
+    peripherals::display::graphics::Color,
+    ui::{Command, CommandAction},
+};
+
+// Kind of device an entity is, taken from the prefix of its id, such as `light` in
+// `light.salon`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Domain {
+    Light,
+    Climate,
+    MediaPlayer,
+    Number,
+    Other,
+}
+
+impl Domain {
+    pub fn of(id: &str) -> Self {
+        match id.split_once('.').map(|(domain, _)| domain) {
+            Some("light") => Self::Light,
+            Some("climate") => Self::Climate,
+            Some("media_player") => Self::MediaPlayer,
+            Some("number" | "input_number" | "fan" | "cover") => Self::Number,
+            _ => Self::Other,
+        }
+    }
+}
+
+// Last known state of an entity. Attributes an entity does not have, or that were never reported,
+// are `None`. Temperatures are in tenths of a degree and percentages from 0 to 100.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntityState {
+    pub on: Option<bool>,
+    pub brightness: Option<u8>,
+    pub color_temperature: Option<u16>,
+    pub color: Option<Color>,
+    pub temperature: Option<i32>,
+    pub target: Option<i32>,
+    pub mode: Option<String>,
+    pub value: Option<i32>,
+    pub volume: Option<u8>,
+    pub playing: Option<bool>,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+}
+
+impl EntityState {
+    // Takes every attribute `report` has, keeping the last known value of the others.
+    pub fn merge(&mut self, report: EntityState) {
+        self.on = report.on.or(self.on);
+        self.brightness = report.brightness.or(self.brightness);
+        self.color_temperature = report.color_temperature.or(self.color_temperature);
+        self.color = report.color.or(self.color);
+        self.temperature = report.temperature.or(self.temperature);
+        self.target = report.target.or(self.target);
+        self.mode = report.mode.or(self.mode.take());
+        self.value = report.value.or(self.value);
+        self.volume = report.volume.or(self.volume);
+        self.playing = report.playing.or(self.playing);
+        self.title = report.title.or(self.title.take());
+        self.artist = report.artist.or(self.artist.take());
+    }
+
+    // Lights turned on at zero brightness come back at full brightness, as the light views do.
+    fn switch(&mut self, on: bool) {
+        self.on = Some(on);
+
+        if on && self.brightness == Some(0) {
+            self.brightness = Some(100);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity {
+    pub id: String,
+    pub domain: Domain,
+    pub state: EntityState,
+}
+
+// State of every entity the knob knows about, kept apart from the views showing it. Changes,
+// whether from input or reported by the entities themselves, are collected until taken with
+// `take_changed` and handed to the views bound to them.
+#[derive(Debug, Default)]
+pub struct EntityStore {
+    entities: Vec<Entity>,
+    // Positions in `entities` changed since the last call to `take_changed`.
+    changed: Vec<usize>,
+}
+
+impl EntityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Entity> {
+        self.entities.iter().find(|entity| entity.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    // Changes the state of an entity, adding it if it is new, and returns whether anything
+    // changed. A new entity counts as changed, as `take_changed` hands it out.
+    pub fn update<F>(&mut self, id: &str, update: F) -> bool
+    where
+        F: FnOnce(&mut EntityState),
+    {
+        let (index, added) = match self.entities.iter().position(|entity| entity.id == id) {
+            Some(index) => (index, false),
+            None => {
+                debug!("Adding entity {}", id);
+
+                self.entities.push(Entity {
+                    id: id.to_string(),
+                    domain: Domain::of(id),
+                    state: EntityState::default(),
+                });
+                self.mark_changed(self.entities.len() - 1);
+
+                (self.entities.len() - 1, true)
+            },
+        };
+
+        let entity = &mut self.entities[index];
+        let mut state = entity.state.clone();
+        update(&mut state);
+
+        if state == entity.state {
+            return added;
+        }
+
+        debug!("Entity {} changed to {:?}", entity.id, state);
+
+        entity.state = state;
+        self.mark_changed(index);
+
+        true
+    }
+
+    // Applies the effect a command is expected to have on its target, so every view showing the
+    // entity follows input before the entity reports its new state.
+    pub fn apply(&mut self, command: &Command) -> bool {
+        let domain = Domain::of(&command.target);
+
+        self.update(&command.target, |state| match command.action {
+            CommandAction::Toggle => state.switch(!state.on.unwrap_or(false)),
+            CommandAction::TurnOn => state.switch(true),
+            CommandAction::TurnOff => state.switch(false),
+            CommandAction::Set(value) => match domain {
+                Domain::Light => {
+                    state.brightness = Some(value.clamp(0, 100) as u8);
+                    state.on = Some(value > 0);
+                },
+                Domain::Climate => state.target = Some(value),
+                Domain::MediaPlayer => state.volume = Some(value.clamp(0, 100) as u8),
+                Domain::Number | Domain::Other => state.value = Some(value),
+            },
+            CommandAction::Step(delta) => {
+                state.value = Some(state.value.unwrap_or(0).saturating_add(delta));
+            },
+            CommandAction::Select(option) => match domain {
+                Domain::MediaPlayer => state.playing = Some(option == "play"),
+                _ => state.mode = Some(option.to_string()),
+            },
+            CommandAction::SetColor(color) => {
+                state.color = Some(color);
+                state.on = Some(true);
+            },
+            CommandAction::Trigger => {},
+        })
+    }
+
+    // Entities changed since the previous call, each once, in the order they first changed.
+    pub fn take_changed(&mut self) -> impl Iterator<Item = &Entity> {
+        let changed = mem::take(&mut self.changed);
+
+        changed
+            .into_iter()
+            .filter_map(|index| self.entities.get(index))
+    }
+
+    fn mark_changed(&mut self, index: usize) {
+        if !self.changed.contains(&index) {
+            self.changed.push(index);
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use core::fmt;
 
+#[cfg(target_os = "none")]
 use esp_hal::{dma::DmaBufError as SpiBufError, spi::master::ConfigError as SpiConfigError};
 
+#[cfg(not(target_os = "none"))]
+use super::{ConfigError as SpiConfigError, DmaBufError as SpiBufError};
+
 #[derive(Debug)]
 pub enum HardwareError {
     Spi(SpiError),
+    Storage(StorageError),
 }
 
 #[derive(Debug)]
@@ -35,12 +40,34 @@ impl SpiError {
     }
 }
 
+#[derive(Debug)]
+pub enum StorageError {
+    PartitionTable(&'static str),
+    ReadFailed(&'static str),
+}
+
+impl StorageError {
+    pub fn partition_table(msg: &'static str) -> Self {
+        Self::PartitionTable(msg)
+    }
+
+    pub fn read_failed(msg: &'static str) -> Self {
+        Self::ReadFailed(msg)
+    }
+}
+
 impl From<SpiError> for HardwareError {
     fn from(err: SpiError) -> Self {
         Self::Spi(err)
     }
 }
 
+impl From<StorageError> for HardwareError {
+    fn from(err: StorageError) -> Self {
+        Self::Storage(err)
+    }
+}
+
 impl From<SpiConfigError> for SpiError {
     fn from(err: SpiConfigError) -> Self {
         Self::Config(err)
@@ -69,6 +96,7 @@ impl fmt::Display for HardwareError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Spi(err) => write!(f, "SPI error: {}", err),
+            Self::Storage(err) => write!(f, "Storage error: {}", err),
         }
     }
 }
@@ -85,3 +113,12 @@ impl fmt::Display for SpiError {
         }
     }
 }
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PartitionTable(msg) => write!(f, "Invalid partition table: {}", msg),
+            Self::ReadFailed(msg) => write!(f, "Flash read failed: {}", msg),
+        }
+    }
+}
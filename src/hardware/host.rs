@@ -0,0 +1,42 @@
+// Stands in for the chip in host builds. The display drives these as it would the panel, and
+// they drop whatever is sent to them.
+
+use core::marker::PhantomData;
+
+#[path = "error.rs"]
+pub mod error;
+
+#[derive(Debug)]
+pub struct DmaBufError;
+
+#[derive(Debug)]
+pub struct ConfigError;
+
+#[derive(Default)]
+pub struct Output<'d>(PhantomData<&'d ()>);
+
+impl Output<'_> {
+    pub fn set_high(&mut self) {}
+
+    pub fn set_low(&mut self) {}
+}
+
+pub mod spi {
+    use super::error::SpiError;
+
+    pub struct SpiInterface;
+
+    impl SpiInterface {
+        pub async fn write(&mut self, _data: &[u8]) -> Result<(), SpiError> {
+            Ok(())
+        }
+
+        pub async fn read(&mut self, _data: &mut [u8]) -> Result<(), SpiError> {
+            Ok(())
+        }
+
+        pub async fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), SpiError> {
+            Ok(())
+        }
+    }
+}
@@ -1,15 +1,17 @@
 pub mod error;
 pub mod spi;
+pub mod storage;
 
+pub use esp_hal::gpio::Output;
 use esp_hal::{
-    gpio::{Level, Output, OutputConfig},
+    gpio::{Level, OutputConfig},
     peripherals::Peripherals,
     spi::Mode,
     timer::systimer::SystemTimer,
 };
 use log::{debug, info};
 
-use self::{error::HardwareError, spi::SpiInterface};
+use self::{error::HardwareError, spi::SpiInterface, storage::Storage};
 
 pub struct Pins {
     pub display_dc: Output<'static>,
@@ -19,6 +21,7 @@ pub struct Pins {
 pub struct Hardware {
     pub display_spi: SpiInterface,
     pub pins: Pins,
+    pub storage: Storage,
 }
 
 impl Hardware {
@@ -46,9 +49,15 @@ impl Hardware {
             display_rst: Output::new(peripherals.GPIO2, Level::High, OutputConfig::default()),
         };
 
+        let storage = Storage::new();
+
         info!("Components initialized successfully");
 
-        Ok(Self { display_spi, pins })
+        Ok(Self {
+            display_spi,
+            pins,
+            storage,
+        })
     }
 
     fn init_peripherals() -> Result<Peripherals, HardwareError> {
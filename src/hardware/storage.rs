@@ -0,0 +1,65 @@
+use alloc::{vec, vec::Vec};
+
+use embedded_storage::ReadStorage;
+use esp_bootloader_esp_idf::partitions::{self, PARTITION_TABLE_MAX_LEN};
+use esp_storage::FlashStorage;
+use log::{debug, info};
+
+use super::error::{HardwareError, StorageError};
+
+// Label of the data partition holding the view configuration. See partitions.csv.
+const CONFIG_LABEL: &str = "config";
+const CONFIG_MAX_LEN: usize = 4096;
+
+// Erased flash reads as 0xFF, which marks where the stored configuration ends.
+const ERASED: u8 = 0xFF;
+
+pub struct Storage {
+    flash: FlashStorage,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Storage {
+    pub fn new() -> Self {
+        Self {
+            flash: FlashStorage::new(),
+        }
+    }
+
+    // Reads the view configuration written to the config partition. None if there is no such
+    // partition or it is erased.
+    pub fn read_config(&mut self) -> Result<Option<Vec<u8>>, HardwareError> {
+        let mut table = [0u8; PARTITION_TABLE_MAX_LEN];
+        let partitions = partitions::read_partition_table(&mut self.flash, &mut table)
+            .map_err(|_| StorageError::partition_table("could not read partition table"))?;
+
+        let Some(partition) = partitions
+            .iter()
+            .find(|partition| partition.label_as_str() == CONFIG_LABEL)
+        else {
+            debug!("No configuration partition");
+            return Ok(None);
+        };
+
+        let mut config = vec![0u8; (partition.len() as usize).min(CONFIG_MAX_LEN)];
+        self.flash
+            .read(partition.offset(), &mut config)
+            .map_err(|_| StorageError::read_failed("could not read configuration"))?;
+
+        if let Some(end) = config.iter().position(|&byte| byte == ERASED) {
+            config.truncate(end);
+        }
+        if config.is_empty() {
+            debug!("Configuration partition is empty");
+            return Ok(None);
+        }
+
+        info!("Read {} bytes of configuration from flash", config.len());
+        Ok(Some(config))
+    }
+}
@@ -2,10 +2,19 @@
 
 extern crate alloc;
 
+// Unit tests build for the host, where there is no chip to run the application on and the
+// hardware is stood in for.
+#[cfg(target_os = "none")]
 pub mod app;
+pub mod assets;
+pub mod clock;
+pub mod config;
+pub mod entity;
 pub mod error;
+#[cfg_attr(not(target_os = "none"), path = "hardware/host.rs")]
 pub mod hardware;
 pub mod peripherals;
 pub mod ui;
 
+#[cfg(target_os = "none")]
 pub use app::App;
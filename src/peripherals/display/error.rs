@@ -6,6 +6,7 @@ use crate::hardware::error::SpiError;
 pub enum DisplayError {
     Spi(SpiError),
     InvalidOperation(&'static str),
+    InvalidColor(&'static str),
     OutOfBounds { x1: u16, y1: u16, x2: u16, y2: u16 },
 }
 
@@ -20,6 +21,7 @@ impl fmt::Display for DisplayError {
         match self {
             Self::Spi(err) => write!(f, "SPI error in display: {}", err),
             Self::InvalidOperation(msg) => write!(f, "Invalid display operation: {}", msg),
+            Self::InvalidColor(msg) => write!(f, "Invalid color: {}", msg),
             Self::OutOfBounds { x1, y1, x2, y2 } => write!(
                 f,
                 "Coordinates out of bounds: ({}, {}) >= ({}, {})",
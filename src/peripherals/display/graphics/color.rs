@@ -1,10 +1,29 @@
-use core::fmt;
+use core::{fmt, str::FromStr};
 
-use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::{pixelcolor::Rgb565, prelude::RgbColor};
+use libm::{logf, powf, roundf};
 
-#[derive(Clone, Copy)]
+use crate::peripherals::display::error::DisplayError;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Color(pub u8, pub u8, pub u8);
 
+// Hue in degrees (0-359), saturation and value from 0 to 255.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hsv {
+    pub hue: u16,
+    pub saturation: u8,
+    pub value: u8,
+}
+
+// Hue in degrees (0-359), saturation and lightness from 0 to 255.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hsl {
+    pub hue: u16,
+    pub saturation: u8,
+    pub lightness: u8,
+}
+
 impl fmt::Debug for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {}, {})", self.0, self.1, self.2)
@@ -24,8 +43,272 @@ impl Color {
         Rgb565::new(self.0 >> 3, self.1 >> 2, self.2 >> 3)
     }
 
+    pub const fn from_rgb565(color: u16) -> Self {
+        let r = ((color >> 11) & 0x1F) as u8;
+        let g = ((color >> 5) & 0x3F) as u8;
+        let b = (color & 0x1F) as u8;
+
+        Self(
+            (r << 3) | (r >> 2),
+            (g << 2) | (g >> 4),
+            (b << 3) | (b >> 2),
+        )
+    }
+
+    pub const fn from_hex(hex: u32) -> Self {
+        Self((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    pub const fn to_hex(&self) -> u32 {
+        ((self.0 as u32) << 16) | ((self.1 as u32) << 8) | (self.2 as u32)
+    }
+
+    pub const fn parse(value: &str) -> Result<Self, DisplayError> {
+        let bytes = value.as_bytes();
+
+        if bytes.is_empty() || bytes[0] != b'#' {
+            return Err(DisplayError::InvalidColor("Color must start with '#'"));
+        }
+
+        let short = match bytes.len() {
+            4 => true,
+            7 => false,
+            _ => return Err(DisplayError::InvalidColor("Color must be #RGB or #RRGGBB")),
+        };
+
+        let mut hex = 0u32;
+        let mut i = 1;
+        while i < bytes.len() {
+            let digit = match bytes[i] {
+                b'0'..=b'9' => bytes[i] - b'0',
+                b'a'..=b'f' => bytes[i] - b'a' + 10,
+                b'A'..=b'F' => bytes[i] - b'A' + 10,
+                _ => return Err(DisplayError::InvalidColor("Invalid hexadecimal digit")),
+            } as u32;
+
+            hex = if short {
+                (hex << 8) | (digit << 4) | digit
+            } else {
+                (hex << 4) | digit
+            };
+            i += 1;
+        }
+
+        Ok(Self::from_hex(hex))
+    }
+
+    pub const fn from_hsv(hsv: Hsv) -> Self {
+        let v = hsv.value as u32;
+        let s = hsv.saturation as u32;
+
+        if s == 0 {
+            return Self(hsv.value, hsv.value, hsv.value);
+        }
+
+        let hue = (hsv.hue % 360) as u32;
+        let remainder = (hue % 60) * 255 / 60;
+
+        let p = (v * (255 - s) / 255) as u8;
+        let q = (v * (255 - s * remainder / 255) / 255) as u8;
+        let t = (v * (255 - s * (255 - remainder) / 255) / 255) as u8;
+        let v = v as u8;
+
+        match hue / 60 {
+            0 => Self(v, t, p),
+            1 => Self(q, v, p),
+            2 => Self(p, v, t),
+            3 => Self(p, q, v),
+            4 => Self(t, p, v),
+            _ => Self(v, p, q),
+        }
+    }
+
+    pub const fn to_hsv(&self) -> Hsv {
+        let (max, min) = self.max_min();
+        let delta = max - min;
+
+        Hsv {
+            hue: self.hue(max, delta),
+            saturation: match (delta * 255).checked_div(max) {
+                Some(saturation) => saturation as u8,
+                None => 0,
+            },
+            value: max as u8,
+        }
+    }
+
+    pub const fn from_hsl(hsl: Hsl) -> Self {
+        let l = hsl.lightness as i32;
+        let chroma = (255 - (2 * l - 255).abs()) * (hsl.saturation as i32) / 255;
+
+        let hue = (hsl.hue % 360) as i32;
+        let x = chroma * (60 - ((hue % 120) - 60).abs()) / 60;
+        let m = l - chroma / 2;
+
+        let (r, g, b) = match hue / 60 {
+            0 => (chroma, x, 0),
+            1 => (x, chroma, 0),
+            2 => (0, chroma, x),
+            3 => (0, x, chroma),
+            4 => (x, 0, chroma),
+            _ => (chroma, 0, x),
+        };
+
+        Self(
+            clamp_channel(r + m),
+            clamp_channel(g + m),
+            clamp_channel(b + m),
+        )
+    }
+
+    pub const fn to_hsl(&self) -> Hsl {
+        let (max, min) = self.max_min();
+        let delta = max - min;
+        let sum = (max + min) as i32;
+
+        Hsl {
+            hue: self.hue(max, delta),
+            saturation: if delta == 0 {
+                0
+            } else {
+                (delta as i32 * 255 / (255 - (sum - 255).abs())) as u8
+            },
+            lightness: (sum / 2) as u8,
+        }
+    }
+
+    // Approximates the color of a black body at the given temperature, clamped to 1000-40000 K.
+    pub fn from_kelvin(kelvin: u16) -> Self {
+        let temperature = kelvin.clamp(1000, 40000) as f32 / 100.0;
+
+        let r = if temperature <= 66.0 {
+            255.0
+        } else {
+            329.698_73 * powf(temperature - 60.0, -0.133_204_76)
+        };
+
+        let g = if temperature <= 66.0 {
+            99.470_8 * logf(temperature) - 161.119_57
+        } else {
+            288.122_17 * powf(temperature - 60.0, -0.075_514_85)
+        };
+
+        let b = if temperature >= 66.0 {
+            255.0
+        } else if temperature <= 19.0 {
+            0.0
+        } else {
+            138.517_73 * logf(temperature - 10.0) - 305.044_8
+        };
+
+        Self(
+            clamp_channel(roundf(r) as i32),
+            clamp_channel(roundf(g) as i32),
+            clamp_channel(roundf(b) as i32),
+        )
+    }
+
+    // Interpolates towards `other`, where `t` goes from 0 (self) to 255 (other).
+    pub const fn lerp(self, other: Color, t: u8) -> Self {
+        Self(
+            lerp_channel(self.0, other.0, t),
+            lerp_channel(self.1, other.1, t),
+            lerp_channel(self.2, other.2, t),
+        )
+    }
+
+    // Scales every channel by `brightness`, from 0 (black) to 255 (unchanged).
+    pub const fn scale(self, brightness: u8) -> Self {
+        Self::BLACK.lerp(self, brightness)
+    }
+
+    pub const fn luminance(&self) -> u8 {
+        ((self.0 as u32 * 54 + self.1 as u32 * 183 + self.2 as u32 * 19) >> 8) as u8
+    }
+
+    const fn max_min(&self) -> (u32, u32) {
+        let (r, g, b) = (self.0 as u32, self.1 as u32, self.2 as u32);
+
+        let max = if r > g { r } else { g };
+        let max = if max > b { max } else { b };
+        let min = if r < g { r } else { g };
+        let min = if min < b { min } else { b };
+
+        (max, min)
+    }
+
+    const fn hue(&self, max: u32, delta: u32) -> u16 {
+        if delta == 0 {
+            return 0;
+        }
+
+        let (r, g, b) = (self.0 as i32, self.1 as i32, self.2 as i32);
+        let delta = delta as i32;
+
+        let hue = if max as i32 == r {
+            60 * (g - b) / delta
+        } else if max as i32 == g {
+            120 + 60 * (b - r) / delta
+        } else {
+            240 + 60 * (r - g) / delta
+        };
+
+        hue.rem_euclid(360) as u16
+    }
+
     pub const BLACK: Color = Color(0, 0, 0);
     pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const DARK_GRAY: Color = Color(64, 64, 64);
+    pub const LIGHT_GRAY: Color = Color(192, 192, 192);
+    pub const RED: Color = Color(255, 0, 0);
+    pub const GREEN: Color = Color(0, 255, 0);
+    pub const BLUE: Color = Color(0, 0, 255);
+    pub const YELLOW: Color = Color(255, 255, 0);
+    pub const CYAN: Color = Color(0, 255, 255);
+    pub const MAGENTA: Color = Color(255, 0, 255);
+    pub const ORANGE: Color = Color(255, 165, 0);
+    pub const AMBER: Color = Color(255, 191, 0);
+    pub const PURPLE: Color = Color(128, 0, 128);
+    pub const TEAL: Color = Color(0, 128, 128);
+    pub const WARM_WHITE: Color = Color(255, 180, 107);
+    pub const COOL_WHITE: Color = Color(201, 218, 255);
+}
+
+const fn lerp_channel(from: u8, to: u8, t: u8) -> u8 {
+    let t = t as u32;
+
+    ((from as u32 * (255 - t) + to as u32 * t + 127) / 255) as u8
+}
+
+const fn clamp_channel(value: i32) -> u8 {
+    if value < 0 {
+        0
+    } else if value > 255 {
+        255
+    } else {
+        value as u8
+    }
+}
+
+impl FromStr for Color {
+    type Err = DisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        Self::from_hsv(hsv)
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        Self::from_hsl(hsl)
+    }
 }
 
 impl From<Color> for u16 {
@@ -39,3 +322,176 @@ impl From<Color> for Rgb565 {
         color.to_embedded_rgb565()
     }
 }
+
+impl From<Rgb565> for Color {
+    fn from(color: Rgb565) -> Self {
+        Self(
+            (color.r() << 3) | (color.r() >> 2),
+            (color.g() << 2) | (color.g() >> 4),
+            (color.b() << 3) | (color.b() >> 2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Conversions use integer maths, so round trips may be off by a few steps per channel.
+    const ROUND_TRIP_TOLERANCE: u8 = 5;
+
+    // Colour at each hue sector boundary, from 0 to 300 degrees.
+    const SECTORS: [(u16, Color); 6] = [
+        (0, Color::RED),
+        (60, Color::YELLOW),
+        (120, Color::GREEN),
+        (180, Color::CYAN),
+        (240, Color::BLUE),
+        (300, Color::MAGENTA),
+    ];
+
+    // Largest difference between the channels of two colours.
+    fn distance(a: Color, b: Color) -> u8 {
+        a.0.abs_diff(b.0)
+            .max(a.1.abs_diff(b.1))
+            .max(a.2.abs_diff(b.2))
+    }
+
+    fn samples() -> impl Iterator<Item = Color> {
+        (0..=255u8).step_by(15).flat_map(|r| {
+            (0..=255u8)
+                .step_by(15)
+                .flat_map(move |g| (0..=255u8).step_by(15).map(move |b| Color(r, g, b)))
+        })
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        for color in samples() {
+            let back = Color::from_hsv(color.to_hsv());
+            assert!(
+                distance(color, back) <= ROUND_TRIP_TOLERANCE,
+                "{:?} came back as {:?}",
+                color,
+                back
+            );
+        }
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for color in samples() {
+            let back = Color::from_hsl(color.to_hsl());
+            assert!(
+                distance(color, back) <= ROUND_TRIP_TOLERANCE,
+                "{:?} came back as {:?}",
+                color,
+                back
+            );
+        }
+    }
+
+    #[test]
+    fn grey_has_no_hue_or_saturation() {
+        for level in [0, 64, 128, 255] {
+            let grey = Color(level, level, level);
+
+            let hsv = grey.to_hsv();
+            assert_eq!((hsv.hue, hsv.saturation, hsv.value), (0, 0, level));
+            let hsl = grey.to_hsl();
+            assert_eq!((hsl.hue, hsl.saturation, hsl.lightness), (0, 0, level));
+
+            for hue in [0, 90, 359] {
+                let hsv = Hsv {
+                    hue,
+                    saturation: 0,
+                    value: level,
+                };
+                assert_eq!(Color::from_hsv(hsv), grey);
+            }
+        }
+    }
+
+    #[test]
+    fn hue_sector_boundaries() {
+        for (hue, color) in SECTORS {
+            let hsv = Hsv {
+                hue,
+                saturation: 255,
+                value: 255,
+            };
+            assert_eq!(Color::from_hsv(hsv), color);
+            assert_eq!(color.to_hsv(), hsv);
+
+            let hsl = Hsl {
+                hue,
+                saturation: 255,
+                lightness: 127,
+            };
+            assert!(distance(Color::from_hsl(hsl), color) <= 1);
+            assert_eq!(color.to_hsl().hue, hue);
+        }
+
+        let wrapped = Hsv {
+            hue: 360,
+            saturation: 255,
+            value: 255,
+        };
+        assert_eq!(Color::from_hsv(wrapped), Color::RED);
+    }
+
+    #[test]
+    fn from_kelvin_clamps() {
+        assert_eq!(Color::from_kelvin(0), Color::from_kelvin(1000));
+        assert_eq!(Color::from_kelvin(u16::MAX), Color::from_kelvin(40000));
+
+        // Red at the warm end, with no blue, and blue-white at the cold end.
+        let warm = Color::from_kelvin(1000);
+        assert_eq!((warm.0, warm.2), (255, 0));
+        let cold = Color::from_kelvin(40000);
+        assert_eq!(cold.2, 255);
+        assert!(cold.0 < cold.2);
+    }
+
+    #[test]
+    fn parse_accepts_hex_colours() {
+        assert_eq!(Color::parse("#FF8000").unwrap(), Color(255, 128, 0));
+        assert_eq!(Color::parse("#ff8000").unwrap(), Color(255, 128, 0));
+        assert_eq!(Color::parse("#000000").unwrap(), Color::BLACK);
+        assert_eq!(Color::parse("#F80").unwrap(), Color(255, 136, 0));
+        assert_eq!("#00FFFF".parse::<Color>().unwrap(), Color::CYAN);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_colours() {
+        for value in [
+            "", "#", "FF8000", "#FF800", "#FF80000", "#GG0000", "#FF 800", "##F800",
+        ] {
+            assert!(Color::parse(value).is_err(), "{:?} was accepted", value);
+        }
+    }
+
+    #[test]
+    fn rgb565_round_trip() {
+        for value in 0..=u16::MAX {
+            let color = Color::from_rgb565(value);
+            assert_eq!(color.to_rgb565(), value);
+            assert_eq!(u16::from(color), value);
+            assert_eq!(Color::from(Rgb565::from(color)), color);
+        }
+
+        // Full channels stay full when widened back to 8 bits.
+        assert_eq!(Color::from_rgb565(Color::WHITE.into()), Color::WHITE);
+    }
+
+    #[test]
+    fn lerp_ends() {
+        let from = Color(10, 200, 30);
+        let to = Color(250, 0, 128);
+
+        assert_eq!(from.lerp(to, 0), from);
+        assert_eq!(from.lerp(to, 255), to);
+        assert_eq!(to.scale(0), Color::BLACK);
+        assert_eq!(to.scale(255), to);
+    }
+}
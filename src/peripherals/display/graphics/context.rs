@@ -0,0 +1,168 @@
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::{Dimensions, DrawTarget, IntoStorage, Pixel, Point, Size},
+    primitives::Rectangle,
+};
+
+use super::{Color, Graphic};
+use crate::peripherals::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH, Display, error::DisplayError};
+
+// Pixels written to the framebuffer and pixels discarded for falling outside of the clip area.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawStats {
+    pub drawn: u32,
+    pub clipped: u32,
+}
+
+impl DrawStats {
+    pub fn since(&self, earlier: DrawStats) -> DrawStats {
+        DrawStats {
+            drawn: self.drawn.wrapping_sub(earlier.drawn),
+            clipped: self.clipped.wrapping_sub(earlier.clipped),
+        }
+    }
+}
+
+// Draw target that maps local coordinates onto the display. Pixels are offset by `origin`,
+// discarded outside of `clip` and blended with the framebuffer according to `opacity`.
+pub struct DrawContext<'a> {
+    display: &'a mut Display,
+    origin: Point,
+    size: Size,
+    clip: Rectangle,
+    opacity: u8,
+}
+
+impl<'a> DrawContext<'a> {
+    pub fn new(display: &'a mut Display) -> Self {
+        let size = Size::new(DISPLAY_WIDTH as u32, DISPLAY_HEIGHT as u32);
+        let clip = display.clip();
+
+        Self {
+            display,
+            origin: Point::zero(),
+            size,
+            clip,
+            opacity: u8::MAX,
+        }
+    }
+
+    // Context for a child area given in local coordinates. The child draws relative to the
+    // top-left corner of `bounds` and cannot draw outside of them.
+    pub fn child(&mut self, bounds: Rectangle) -> DrawContext<'_> {
+        let absolute = Rectangle::new(self.origin + bounds.top_left, bounds.size);
+
+        DrawContext {
+            display: self.display,
+            origin: absolute.top_left,
+            size: bounds.size,
+            clip: self.clip.intersection(&absolute),
+            opacity: self.opacity,
+        }
+    }
+
+    // Context sharing these bounds with the origin moved by `offset`, without clipping further.
+    pub fn translate(&mut self, offset: Point) -> DrawContext<'_> {
+        DrawContext {
+            display: self.display,
+            origin: self.origin + offset,
+            size: self.size,
+            clip: self.clip,
+            opacity: self.opacity,
+        }
+    }
+
+    // Context that only draws inside `area`, given in local coordinates.
+    pub fn clip(&mut self, area: Rectangle) -> DrawContext<'_> {
+        let absolute = Rectangle::new(self.origin + area.top_left, area.size);
+
+        DrawContext {
+            display: self.display,
+            origin: self.origin,
+            size: self.size,
+            clip: self.clip.intersection(&absolute),
+            opacity: self.opacity,
+        }
+    }
+
+    // Context whose opacity is this one's multiplied by `opacity`, from 0 (invisible) to 255.
+    pub fn with_opacity(&mut self, opacity: u8) -> DrawContext<'_> {
+        DrawContext {
+            display: self.display,
+            origin: self.origin,
+            size: self.size,
+            clip: self.clip,
+            opacity: ((self.opacity as u16 * opacity as u16 + 127) / 255) as u8,
+        }
+    }
+
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn opacity(&self) -> u8 {
+        self.opacity
+    }
+
+    // Clip rectangle in local coordinates.
+    pub fn clip_area(&self) -> Rectangle {
+        Rectangle::new(self.clip.top_left - self.origin, self.clip.size)
+    }
+
+    pub fn draw<T>(&mut self, shape: &T) -> Result<(), DisplayError>
+    where
+        T: Graphic,
+    {
+        shape.draw(self)
+    }
+
+    // Draws a pixel with the given coverage, from 0 (transparent) to 255 (opaque), on top of the
+    // context opacity.
+    pub fn blend_pixel(&mut self, point: Point, color: u16, alpha: u8) {
+        let absolute = point + self.origin;
+        let alpha = ((self.opacity as u16 * alpha as u16 + 127) / 255) as u8;
+
+        if alpha == 0 {
+            return;
+        }
+
+        if !self.clip.contains(absolute) {
+            self.display.stats.clipped = self.display.stats.clipped.wrapping_add(1);
+            return;
+        }
+
+        let (x, y) = (absolute.x as u16, absolute.y as u16);
+        let color = if alpha == u8::MAX {
+            color
+        } else {
+            Color::from_rgb565(self.display.get_pixel(x, y))
+                .lerp(Color::from_rgb565(color), alpha)
+                .to_rgb565()
+        };
+
+        self.display.set_pixel(x, y, color);
+        self.display.stats.drawn = self.display.stats.drawn.wrapping_add(1);
+    }
+}
+
+impl Dimensions for DrawContext<'_> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+}
+
+impl DrawTarget for DrawContext<'_> {
+    type Color = Rgb565;
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.blend_pixel(point, color.into_storage(), u8::MAX);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use embedded_graphics::{pixelcolor::Rgb565, prelude::Point};
+use libm::{atan2f, fmodf, sqrtf};
+
+use super::Color;
+
+// Fixed-point scale of the interpolation position.
+const POSITION_SCALE: u32 = 4096;
+
+#[rustfmt::skip]
+const BAYER_4X4: [[u32; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+// Angles are in degrees, starting at 3 o'clock and increasing clockwise, like embedded-graphics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gradient {
+    Linear {
+        x1: u16,
+        y1: u16,
+        x2: u16,
+        y2: u16,
+        from: Color,
+        to: Color,
+    },
+    Radial {
+        x: u16,
+        y: u16,
+        radius: u16,
+        from: Color,
+        to: Color,
+    },
+    Conic {
+        x: u16,
+        y: u16,
+        angle: f32,
+        from: Color,
+        to: Color,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fill {
+    Solid(Color),
+    Gradient(Gradient),
+}
+
+impl Gradient {
+    // Position of a point along the gradient, from 0 (`from`) to 1 (`to`).
+    fn position(&self, point: Point) -> f32 {
+        let px = point.x as f32 + 0.5;
+        let py = point.y as f32 + 0.5;
+
+        let position = match *self {
+            Self::Linear { x1, y1, x2, y2, .. } => {
+                let dx = x2 as f32 - x1 as f32;
+                let dy = y2 as f32 - y1 as f32;
+                let length = dx * dx + dy * dy;
+
+                if length == 0.0 {
+                    return 0.0;
+                }
+
+                ((px - x1 as f32) * dx + (py - y1 as f32) * dy) / length
+            },
+            Self::Radial { x, y, radius, .. } => {
+                if radius == 0 {
+                    return 1.0;
+                }
+
+                let dx = px - x as f32;
+                let dy = py - y as f32;
+
+                sqrtf(dx * dx + dy * dy) / radius as f32
+            },
+            Self::Conic { x, y, angle, .. } => {
+                let degrees = atan2f(py - y as f32, px - x as f32).to_degrees();
+                let turn = fmodf(degrees - angle, 360.0);
+
+                if turn < 0.0 {
+                    (turn + 360.0) / 360.0
+                } else {
+                    turn / 360.0
+                }
+            },
+        };
+
+        position.clamp(0.0, 1.0)
+    }
+
+    fn colors(&self) -> (Color, Color) {
+        match *self {
+            Self::Linear { from, to, .. }
+            | Self::Radial { from, to, .. }
+            | Self::Conic { from, to, .. } => (from, to),
+        }
+    }
+
+    pub fn color_at(&self, point: Point) -> Rgb565 {
+        let (from, to) = self.colors();
+        let position = (self.position(point) * POSITION_SCALE as f32) as u32;
+        let threshold = BAYER_4X4[(point.y & 3) as usize][(point.x & 3) as usize];
+
+        Rgb565::new(
+            dither(from.0, to.0, position, threshold, 5),
+            dither(from.1, to.1, position, threshold, 6),
+            dither(from.2, to.2, position, threshold, 5),
+        )
+    }
+}
+
+// Interpolates a channel and quantizes it to `bits` bits, using the Bayer threshold to spread the
+// rounding error so gradients do not band.
+fn dither(from: u8, to: u8, position: u32, threshold: u32, bits: u32) -> u8 {
+    let value = from as u32 * (POSITION_SCALE - position) + to as u32 * position;
+
+    let shift = 8 - bits + POSITION_SCALE.trailing_zeros();
+    let offset = (threshold << shift) / 16;
+    let max = (1 << bits) - 1;
+
+    ((value + offset) >> shift).min(max) as u8
+}
+
+impl Fill {
+    pub fn color_at(&self, point: Point) -> Rgb565 {
+        match self {
+            Self::Solid(color) => color.to_embedded_rgb565(),
+            Self::Gradient(gradient) => gradient.color_at(point),
+        }
+    }
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Gradient> for Fill {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
@@ -0,0 +1,57 @@
+use core::ptr;
+
+use embedded_graphics::{
+    pixelcolor::{Rgb565, raw::RawU16},
+    prelude::{DrawTarget, Pixel, Point, Size},
+    primitives::Rectangle,
+};
+use log::debug;
+
+use super::{DrawContext, Graphic};
+use crate::{assets::Image, peripherals::display::error::DisplayError};
+
+#[derive(Clone)]
+pub struct Icon {
+    pub image: &'static Image,
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Graphic for Icon {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing {}x{} icon at ({}, {})",
+            self.image.width, self.image.height, self.x, self.y
+        );
+
+        let width = self.image.width as i32;
+        let Point { x: left, y: top } = self.bounds().top_left;
+
+        let pixels = self.image.pixels().enumerate().filter_map(|(i, color)| {
+            let color = color?;
+            let position = Point::new(left + i as i32 % width, top + i as i32 / width);
+
+            Some(Pixel(position, Rgb565::from(RawU16::new(color))))
+        });
+
+        context.draw_iter(pixels)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let top_left = Point::new(
+            self.x as i32 - self.image.width as i32 / 2,
+            self.y as i32 - self.image.height as i32 / 2,
+        );
+
+        Rectangle::new(
+            top_left,
+            Size::new(self.image.width as u32, self.image.height as u32),
+        )
+    }
+}
+
+impl PartialEq for Icon {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.image, other.image) && self.x == other.x && self.y == other.y
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use libm::sqrtf;
+
+use super::{Alignment, FontSize, Text};
+use crate::peripherals::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+
+const ELLIPSIS: &str = "...";
+
+// What to do with text wider than the visible part of the panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    // Step down font sizes, ellipsizing if the smallest still overflows.
+    Shrink,
+    // Break into lines at word boundaries, centred vertically on the original position.
+    Wrap,
+    // Cut each line and end it with an ellipsis.
+    Ellipsize,
+}
+
+// Width of the round panel visible on row `y`, zero outside the panel.
+pub fn chord_width(y: i32) -> u16 {
+    let radius = DISPLAY_WIDTH as f32 / 2.0;
+    let dy = y as f32 + 0.5 - DISPLAY_HEIGHT as f32 / 2.0;
+
+    if dy.abs() >= radius {
+        return 0;
+    }
+
+    (2.0 * sqrtf(radius * radius - dy * dy)) as u16
+}
+
+// Horizontal span visible across all rows of a band, as (left, right). The narrowest row of a
+// band is always the one furthest from the centre.
+fn chord_span(top: i32, height: u32) -> (i32, i32) {
+    let bottom = top + height.max(1) as i32 - 1;
+    let width = chord_width(top).min(chord_width(bottom)) as i32;
+    let left = (DISPLAY_WIDTH as i32 - width) / 2;
+
+    (left, left + width)
+}
+
+impl Text {
+    // Width available to a line whose middle sits on row `y`, keeping `padding` pixels from the
+    // edge of the panel.
+    fn line_width(&self, y: i32, size: FontSize, padding: u16) -> u32 {
+        let height = size.char_height();
+        let (left, right) = chord_span(y - height as i32 / 2, height);
+        let (left, right) = (left + padding as i32, right - padding as i32);
+        let x = self.x as i32;
+
+        let width = match self.alignment {
+            Alignment::Left => right - x,
+            Alignment::Right => x - left,
+            Alignment::Center => 2 * (x - left).min(right - x),
+        };
+
+        width.max(0) as u32
+    }
+
+    // Width available to the text at its current position and size.
+    pub fn available_width(&self, padding: u16) -> u32 {
+        let lines = self.content.split('\n').count() as i32;
+        let line_height = self.size.line_height() as i32;
+
+        (0..lines)
+            .map(|line| self.line_width(self.y as i32 + line * line_height, self.size, padding))
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub fn fits(&self, padding: u16) -> bool {
+        let line_height = self.size.line_height() as i32;
+
+        self.content.split('\n').enumerate().all(|(line, content)| {
+            let y = self.y as i32 + line as i32 * line_height;
+            let width = self.line_width(y, self.size, padding);
+
+            self.size.width_of(content.chars().count()) <= width
+        })
+    }
+
+    // Returns a copy of the text laid out to stay within the round panel.
+    pub fn fit_to_circle(&self, overflow: Overflow, padding: u16) -> Text {
+        if self.fits(padding) {
+            return self.clone();
+        }
+
+        match overflow {
+            Overflow::Shrink => self.shrink(padding),
+            Overflow::Wrap => self.wrap(padding),
+            Overflow::Ellipsize => self.ellipsize(padding),
+        }
+    }
+
+    fn shrink(&self, padding: u16) -> Text {
+        let mut text = self.clone();
+
+        while let Some(size) = text.size.smaller() {
+            text.size = size;
+
+            if text.fits(padding) {
+                return text;
+            }
+        }
+
+        text.ellipsize(padding)
+    }
+
+    fn ellipsize(&self, padding: u16) -> Text {
+        let line_height = self.size.line_height() as i32;
+
+        let lines: Vec<String> = self
+            .content
+            .split('\n')
+            .enumerate()
+            .map(|(line, content)| {
+                let y = self.y as i32 + line as i32 * line_height;
+                let chars = self.size.chars_in(self.line_width(y, self.size, padding));
+
+                truncate(content, chars)
+            })
+            .collect();
+
+        Text {
+            content: lines.join("\n"),
+            ..self.clone()
+        }
+    }
+
+    fn wrap(&self, padding: u16) -> Text {
+        let words: Vec<&str> = self.content.split_whitespace().collect();
+        let line_height = self.size.line_height() as i32;
+        let mut wrapped = self.clone();
+
+        // Try increasing line counts, re-centring the block each time, until every word fits or
+        // the block no longer fits vertically.
+        for lines in 1..=DISPLAY_HEIGHT as i32 / line_height {
+            let top = self.y as i32 - (lines - 1) * line_height / 2;
+            if top < 0 {
+                break;
+            }
+
+            let widths = (0..lines).map(|line| {
+                let y = top + line * line_height;
+                self.size.chars_in(self.line_width(y, self.size, padding))
+            });
+
+            let (content, complete) = fill_lines(&words, widths);
+            wrapped.content = content;
+            wrapped.y = top as u16;
+
+            if complete {
+                return wrapped;
+            }
+        }
+
+        wrapped.ellipsize(padding)
+    }
+}
+
+// Greedily places words on lines of the given widths in characters. Words longer than a line are
+// broken. Returns the lines and whether every word was placed.
+fn fill_lines(words: &[&str], widths: impl Iterator<Item = usize>) -> (String, bool) {
+    let mut lines: Vec<String> = Vec::new();
+    let mut remaining = words.iter().map(|word| word.to_string()).peekable();
+    let mut pending: Option<String> = None;
+
+    for width in widths {
+        let mut line = String::new();
+
+        while let Some(word) = pending.take().or_else(|| remaining.next()) {
+            let separator = if line.is_empty() { 0 } else { 1 };
+            let length = word.chars().count();
+
+            if line.chars().count() + separator + length <= width {
+                if separator > 0 {
+                    line.push(' ');
+                }
+                line.push_str(&word);
+            } else if line.is_empty() && width > 0 {
+                let split = word
+                    .char_indices()
+                    .nth(width)
+                    .map_or(word.len(), |(i, _)| i);
+                line.push_str(&word[..split]);
+                pending = Some(word[split..].to_string());
+                break;
+            } else {
+                pending = Some(word);
+                break;
+            }
+        }
+
+        lines.push(line);
+    }
+
+    let complete = pending.is_none() && remaining.peek().is_none();
+
+    // Mark dropped words so the caller's ellipsizing keeps a visible hint of them.
+    if !complete && let Some(last) = lines.last_mut() {
+        last.push_str(ELLIPSIS);
+    }
+
+    (lines.join("\n"), complete)
+}
+
+fn truncate(content: &str, chars: usize) -> String {
+    if content.chars().count() <= chars {
+        return content.to_string();
+    }
+
+    if chars <= ELLIPSIS.len() {
+        return content.chars().take(chars).collect();
+    }
+
+    let mut truncated: String = content.chars().take(chars - ELLIPSIS.len()).collect();
+    truncated.truncate(truncated.trim_end().len());
+    truncated.push_str(ELLIPSIS);
+    truncated
+}
@@ -1,16 +1,34 @@
 mod color;
+mod context;
+mod gradient;
+mod icon;
+mod layout;
 mod primitives;
+mod shapes;
+mod sprite;
+mod style;
 mod text;
 
 use embedded_graphics::{
     pixelcolor::Rgb565,
-    prelude::{Dimensions, DrawTarget, IntoStorage, OriginDimensions, Pixel, Size},
+    prelude::{DrawTarget, IntoStorage, OriginDimensions, Pixel},
+};
+pub use embedded_graphics::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
 };
 
 pub use self::{
-    color::Color,
-    primitives::FilledCircle,
-    text::{Alignment, Text},
+    color::{Color, Hsl, Hsv},
+    context::{DrawContext, DrawStats},
+    gradient::{Fill, Gradient},
+    icon::Icon,
+    layout::{Overflow, chord_width},
+    primitives::{FilledArc, FilledCircle, FilledRectangle},
+    shapes::{Ellipse, Line, Polygon, Polyline, RoundedRectangle, Triangle},
+    sprite::{Sampling, Sprite},
+    style::{Stroke, Style},
+    text::{Alignment, FontSize, FontText, Text, TextMetrics},
 };
 use crate::peripherals::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH, Display, error::DisplayError};
 
@@ -28,30 +46,44 @@ impl DrawTarget for Display {
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        let bb = self.bounding_box();
+        let clip = self.clip();
+
+        for Pixel(position, color) in pixels {
+            if !clip.contains(position) {
+                self.stats.clipped = self.stats.clipped.wrapping_add(1);
+                continue;
+            }
 
-        pixels
-            .into_iter()
-            .filter(|&Pixel(pos, _color)| bb.contains(pos))
-            .for_each(|Pixel(position, color)| {
-                let raw_color = color.into_storage();
+            let raw_color = color.into_storage();
 
-                self.set_pixel(position.x as u16, position.y as u16, raw_color);
-            });
+            self.set_pixel(position.x as u16, position.y as u16, raw_color);
+            self.stats.drawn = self.stats.drawn.wrapping_add(1);
+        }
 
         Ok(())
     }
 }
 
 pub trait Graphic {
-    fn draw(&self, display: &mut Display);
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError>;
+
+    // Area covered by the graphic, in the coordinates of the context it is drawn into.
+    fn bounds(&self) -> Rectangle;
 }
 
 impl Display {
-    pub fn draw<T>(&mut self, shape: &T)
+    pub fn draw<T>(&mut self, shape: &T) -> Result<DrawStats, DisplayError>
     where
         T: Graphic,
     {
-        shape.draw(self);
+        let before = self.stats;
+        shape.draw(&mut DrawContext::new(self))?;
+
+        Ok(self.stats.since(before))
+    }
+
+    // Pixels drawn and clipped since the last call to `clear`.
+    pub fn stats(&self) -> DrawStats {
+        self.stats
     }
 }
@@ -1,32 +1,144 @@
 use embedded_graphics::{
-    prelude::{Drawable, Point, Primitive},
-    primitives::{Circle as EgCircle, PrimitiveStyle},
+    geometry::AngleUnit,
+    prelude::{Dimensions, DrawTarget, Pixel, Point, PointsIter, Size},
+    primitives::{Circle as EgCircle, ContainsPoint, Rectangle, Sector as EgSector},
 };
 use log::debug;
 
-use super::{Color, Graphic};
-use crate::peripherals::display::Display;
+use super::{DrawContext, Fill, Graphic};
+use crate::peripherals::display::error::DisplayError;
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct FilledCircle {
     pub x: u16,
     pub y: u16,
     pub diameter: u16,
-    pub color: Color,
+    pub fill: Fill,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilledRectangle {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub fill: Fill,
+}
+
+// Ring segment centred on (x, y). Angles are in degrees, starting at 3 o'clock and increasing
+// clockwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilledArc {
+    pub x: u16,
+    pub y: u16,
+    pub diameter: u16,
+    pub thickness: u16,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub fill: Fill,
+}
+
+impl FilledCircle {
+    fn circle(&self) -> EgCircle {
+        let center = Point::new(self.x as i32, self.y as i32);
+
+        EgCircle::with_center(center, self.diameter as u32)
+    }
+}
+
+impl FilledRectangle {
+    fn rectangle(&self) -> Rectangle {
+        let top_left = Point::new(self.x as i32, self.y as i32);
+
+        Rectangle::new(top_left, Size::new(self.width as u32, self.height as u32))
+    }
+}
+
+impl FilledArc {
+    fn sector(&self) -> EgSector {
+        let center = Point::new(self.x as i32, self.y as i32);
+
+        EgSector::with_center(
+            center,
+            self.diameter as u32,
+            self.start_angle.deg(),
+            self.sweep_angle.deg(),
+        )
+    }
+}
+
+impl DrawContext<'_> {
+    pub(super) fn fill_points<I>(&mut self, points: I, fill: &Fill) -> Result<(), DisplayError>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let pixels = points
+            .into_iter()
+            .map(|point| Pixel(point, fill.color_at(point)));
+
+        self.draw_iter(pixels)
+    }
 }
 
 impl Graphic for FilledCircle {
-    fn draw(&self, display: &mut Display) {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing filled circle at ({}, {}) with diameter {} and fill {:?}",
+            self.x, self.y, self.diameter, self.fill
+        );
+
+        context.fill_points(self.circle().points(), &self.fill)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.circle().bounding_box()
+    }
+}
+
+impl Graphic for FilledRectangle {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
         debug!(
-            "Drawing filled circle at ({}, {}) with radius {} and color {:?}",
-            self.x, self.y, self.diameter, self.color
+            "Drawing filled rectangle at ({}, {}) with size {}x{} and fill {:?}",
+            self.x, self.y, self.width, self.height, self.fill
+        );
+
+        context.fill_points(self.rectangle().points(), &self.fill)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.rectangle()
+    }
+}
+
+impl Graphic for FilledArc {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing filled arc at ({}, {}) with diameter {}, thickness {}, angles {}..{} and \
+             fill {:?}",
+            self.x,
+            self.y,
+            self.diameter,
+            self.thickness,
+            self.start_angle,
+            self.start_angle + self.sweep_angle,
+            self.fill
         );
 
         let center = Point::new(self.x as i32, self.y as i32);
-        let color = self.color.into();
+        let inner_diameter = self
+            .diameter
+            .saturating_sub(self.thickness.saturating_mul(2));
+        let hole = EgCircle::with_center(center, inner_diameter as u32);
 
-        let circle = EgCircle::with_center(center, self.diameter as u32)
-            .into_styled(PrimitiveStyle::with_fill(color));
+        let points = self
+            .sector()
+            .points()
+            .filter(|&point| inner_diameter == 0 || !hole.contains(point));
+
+        context.fill_points(points, &self.fill)
+    }
 
-        let _ = circle.draw(display);
+    fn bounds(&self) -> Rectangle {
+        self.sector().bounding_box()
     }
 }
@@ -0,0 +1,296 @@
+use alloc::vec::Vec;
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::{Dimensions, Point, PointsIter, Size},
+    primitives::{
+        Ellipse as EgEllipse, Line as EgLine, Polyline as EgPolyline, PrimitiveStyle, Rectangle,
+        RoundedRectangle as EgRoundedRectangle, StyledDimensions, StyledDrawable,
+        Triangle as EgTriangle,
+    },
+};
+use libm::{ceilf, floorf};
+use log::debug;
+
+use super::{DrawContext, Fill, Graphic, Stroke, Style};
+use crate::peripherals::display::error::DisplayError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundedRectangle {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub radius: u16,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub x1: u16,
+    pub y1: u16,
+    pub x2: u16,
+    pub y2: u16,
+    pub stroke: Stroke,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point>,
+    pub stroke: Stroke,
+}
+
+// Closed shape through `points`, filled with the even-odd rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Point>,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triangle {
+    pub x1: u16,
+    pub y1: u16,
+    pub x2: u16,
+    pub y2: u16,
+    pub x3: u16,
+    pub y3: u16,
+    pub style: Style,
+}
+
+// Ellipse centred on (x, y).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ellipse {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub style: Style,
+}
+
+impl DrawContext<'_> {
+    fn draw_styled<P>(&mut self, primitive: &P, style: &Style) -> Result<(), DisplayError>
+    where
+        P: PointsIter + StyledDrawable<PrimitiveStyle<Rgb565>, Color = Rgb565>,
+    {
+        if let Some(fill) = &style.fill {
+            self.fill_points(primitive.points(), fill)?;
+        }
+
+        if let Some(stroke) = &style.stroke {
+            self.draw_stroke(primitive, stroke)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_stroke<P>(&mut self, primitive: &P, stroke: &Stroke) -> Result<(), DisplayError>
+    where
+        P: StyledDrawable<PrimitiveStyle<Rgb565>, Color = Rgb565>,
+    {
+        if stroke.width > 0 {
+            primitive.draw_styled(&stroke.primitive_style(), self)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn styled_bounds<P>(primitive: &P, stroke: Option<&Stroke>) -> Rectangle
+where
+    P: Dimensions + StyledDimensions<PrimitiveStyle<Rgb565>>,
+{
+    match stroke {
+        Some(stroke) => primitive.styled_bounding_box(&stroke.primitive_style()),
+        None => primitive.bounding_box(),
+    }
+}
+
+fn point(x: u16, y: u16) -> Point {
+    Point::new(x as i32, y as i32)
+}
+
+impl RoundedRectangle {
+    fn primitive(&self) -> EgRoundedRectangle {
+        let rectangle = Rectangle::new(
+            point(self.x, self.y),
+            Size::new(self.width as u32, self.height as u32),
+        );
+
+        EgRoundedRectangle::with_equal_corners(rectangle, Size::new_equal(self.radius as u32))
+    }
+}
+
+impl Graphic for RoundedRectangle {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing rounded rectangle at ({}, {}) with size {}x{}, radius {} and style {:?}",
+            self.x, self.y, self.width, self.height, self.radius, self.style
+        );
+
+        context.draw_styled(&self.primitive(), &self.style)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(&self.primitive(), self.style.stroke.as_ref())
+    }
+}
+
+impl Line {
+    fn primitive(&self) -> EgLine {
+        EgLine::new(point(self.x1, self.y1), point(self.x2, self.y2))
+    }
+}
+
+impl Graphic for Line {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing line from ({}, {}) to ({}, {}) with stroke {:?}",
+            self.x1, self.y1, self.x2, self.y2, self.stroke
+        );
+
+        context.draw_stroke(&self.primitive(), &self.stroke)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(&self.primitive(), Some(&self.stroke))
+    }
+}
+
+impl Graphic for Polyline {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing polyline with {} points and stroke {:?}",
+            self.points.len(),
+            self.stroke
+        );
+
+        context.draw_stroke(&EgPolyline::new(&self.points), &self.stroke)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(&EgPolyline::new(&self.points), Some(&self.stroke))
+    }
+}
+
+impl Polygon {
+    fn outline(&self) -> Vec<Point> {
+        let mut outline = self.points.clone();
+        outline.extend(self.points.first());
+        outline
+    }
+
+    // Fills the rows of the polygon, sampling each pixel at its centre.
+    fn fill(&self, context: &mut DrawContext, fill: &Fill) -> Result<(), DisplayError> {
+        let bounds = EgPolyline::new(&self.points).bounding_box();
+        let mut crossings = Vec::new();
+
+        for y in bounds.rows() {
+            let center = y as f32 + 0.5;
+
+            crossings.clear();
+            for (i, start) in self.points.iter().enumerate() {
+                let end = self.points[(i + 1) % self.points.len()];
+                let (y1, y2) = (start.y as f32, end.y as f32);
+
+                if (y1 <= center) != (y2 <= center) {
+                    let t = (center - y1) / (y2 - y1);
+                    crossings.push(start.x as f32 + t * (end.x - start.x) as f32);
+                }
+            }
+
+            crossings.sort_unstable_by(f32::total_cmp);
+
+            for span in crossings.chunks_exact(2) {
+                let left = ceilf(span[0] - 0.5) as i32;
+                let right = floorf(span[1] - 0.5) as i32;
+
+                context.fill_points((left..=right).map(|x| Point::new(x, y)), fill)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Graphic for Polygon {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing polygon with {} points and style {:?}",
+            self.points.len(),
+            self.style
+        );
+
+        if self.points.len() < 3 {
+            return Err(DisplayError::InvalidOperation(
+                "Polygons need at least three points",
+            ));
+        }
+
+        if let Some(fill) = &self.style.fill {
+            self.fill(context, fill)?;
+        }
+
+        if let Some(stroke) = &self.style.stroke {
+            context.draw_stroke(&EgPolyline::new(&self.outline()), stroke)?;
+        }
+
+        Ok(())
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(
+            &EgPolyline::new(&self.outline()),
+            self.style.stroke.as_ref(),
+        )
+    }
+}
+
+impl Triangle {
+    fn primitive(&self) -> EgTriangle {
+        EgTriangle::new(
+            point(self.x1, self.y1),
+            point(self.x2, self.y2),
+            point(self.x3, self.y3),
+        )
+    }
+}
+
+impl Graphic for Triangle {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing triangle ({}, {}), ({}, {}), ({}, {}) with style {:?}",
+            self.x1, self.y1, self.x2, self.y2, self.x3, self.y3, self.style
+        );
+
+        context.draw_styled(&self.primitive(), &self.style)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(&self.primitive(), self.style.stroke.as_ref())
+    }
+}
+
+impl Ellipse {
+    fn primitive(&self) -> EgEllipse {
+        EgEllipse::with_center(
+            point(self.x, self.y),
+            Size::new(self.width as u32, self.height as u32),
+        )
+    }
+}
+
+impl Graphic for Ellipse {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing ellipse at ({}, {}) with size {}x{} and style {:?}",
+            self.x, self.y, self.width, self.height, self.style
+        );
+
+        context.draw_styled(&self.primitive(), &self.style)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        styled_bounds(&self.primitive(), self.style.stroke.as_ref())
+    }
+}
@@ -0,0 +1,225 @@
+use core::ptr;
+
+use embedded_graphics::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+use libm::{ceilf, cosf, floorf, sinf};
+use log::debug;
+
+use super::{Color, DrawContext, DrawStats, Graphic};
+use crate::{
+    assets::{Image, ImageFormat},
+    peripherals::display::{Display, error::DisplayError},
+};
+
+// Fractional bits of the fixed-point source coordinates.
+const FRACTION_BITS: u32 = 16;
+const ONE: i32 = 1 << FRACTION_BITS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sampling {
+    Nearest,
+    Bilinear,
+}
+
+// Image drawn rotated by `angle` degrees clockwise and scaled around its pivot, which lands on
+// (x, y). The image must be stored as RGB565 so its pixels can be sampled in any order.
+#[derive(Clone)]
+pub struct Sprite {
+    pub image: &'static Image,
+    pub x: u16,
+    pub y: u16,
+    pub pivot_x: u16,
+    pub pivot_y: u16,
+    pub angle: f32,
+    pub scale: f32,
+    pub sampling: Sampling,
+}
+
+impl Sprite {
+    fn validate(&self) -> Result<(), DisplayError> {
+        if self.image.format != ImageFormat::Rgb565 {
+            return Err(DisplayError::InvalidOperation(
+                "Sprites must be stored as RGB565",
+            ));
+        }
+
+        if self.scale <= 0.0 {
+            return Err(DisplayError::InvalidOperation(
+                "Sprite scale must be positive",
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Destination area covered by the transformed image, clipped to `clip`.
+    fn destination(&self, cos: f32, sin: f32, clip: (i32, i32, i32, i32)) -> (i32, i32, i32, i32) {
+        let corners = [
+            (0.0, 0.0),
+            (self.image.width as f32, 0.0),
+            (0.0, self.image.height as f32),
+            (self.image.width as f32, self.image.height as f32),
+        ];
+
+        let (mut left, mut top) = (f32::MAX, f32::MAX);
+        let (mut right, mut bottom) = (f32::MIN, f32::MIN);
+
+        for (cx, cy) in corners {
+            let dx = (cx - self.pivot_x as f32) * self.scale;
+            let dy = (cy - self.pivot_y as f32) * self.scale;
+            let x = self.x as f32 + dx * cos - dy * sin;
+            let y = self.y as f32 + dx * sin + dy * cos;
+
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+
+        (
+            (floorf(left) as i32).max(clip.0),
+            (floorf(top) as i32).max(clip.1),
+            (ceilf(right) as i32).min(clip.2),
+            (ceilf(bottom) as i32).min(clip.3),
+        )
+    }
+
+    fn sample(&self, u: i32, v: i32) -> Option<(u16, u8)> {
+        match self.sampling {
+            Sampling::Nearest => {
+                let (x, y) = (u >> FRACTION_BITS, v >> FRACTION_BITS);
+                self.texel(x, y).map(|color| (color, u8::MAX))
+            },
+            Sampling::Bilinear => {
+                // Shift by half a texel so weights are measured between texel centres.
+                let (u, v) = (u - ONE / 2, v - ONE / 2);
+                let (x, y) = (u >> FRACTION_BITS, v >> FRACTION_BITS);
+                let fx = ((u & (ONE - 1)) >> 8) as u32;
+                let fy = ((v & (ONE - 1)) >> 8) as u32;
+
+                let neighbours = [
+                    (self.texel(x, y), (256 - fx) * (256 - fy)),
+                    (self.texel(x + 1, y), fx * (256 - fy)),
+                    (self.texel(x, y + 1), (256 - fx) * fy),
+                    (self.texel(x + 1, y + 1), fx * fy),
+                ];
+
+                let (mut r, mut g, mut b, mut weight) = (0, 0, 0, 0);
+                for (color, w) in neighbours {
+                    if let Some(color) = color {
+                        let color = Color::from_rgb565(color);
+                        r += color.0 as u32 * w;
+                        g += color.1 as u32 * w;
+                        b += color.2 as u32 * w;
+                        weight += w;
+                    }
+                }
+
+                if weight == 0 {
+                    return None;
+                }
+
+                let color = Color((r / weight) as u8, (g / weight) as u8, (b / weight) as u8);
+                let alpha = (weight * 255 / (256 * 256)) as u8;
+
+                Some((color.to_rgb565(), alpha))
+            },
+        }
+    }
+
+    fn texel(&self, x: i32, y: i32) -> Option<u16> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        self.image.pixel(x as u16, y as u16)
+    }
+}
+
+impl Graphic for Sprite {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing sprite at ({}, {}) rotated {} degrees and scaled {}",
+            self.x, self.y, self.angle, self.scale
+        );
+
+        self.validate()?;
+
+        let radians = self.angle.to_radians();
+        let (cos, sin) = (cosf(radians), sinf(radians));
+
+        let clip = context.clip_area();
+        let clip = (
+            clip.top_left.x,
+            clip.top_left.y,
+            clip.top_left.x + clip.size.width as i32,
+            clip.top_left.y + clip.size.height as i32,
+        );
+        let (left, top, right, bottom) = self.destination(cos, sin, clip);
+
+        // Inverse transform from destination to source, stepped one pixel at a time.
+        let step_x = ((cos / self.scale) * ONE as f32) as i32;
+        let step_y = ((sin / self.scale) * ONE as f32) as i32;
+        let pivot_u = (self.pivot_x as i32) << FRACTION_BITS;
+        let pivot_v = (self.pivot_y as i32) << FRACTION_BITS;
+
+        for y in top..bottom {
+            // Offsets of the first pixel centre from the pivot, in fixed point.
+            let dx = ((left - self.x as i32) << FRACTION_BITS) + ONE / 2;
+            let dy = ((y - self.y as i32) << FRACTION_BITS) + ONE / 2;
+
+            let mut u = pivot_u + fixed_mul(dx, step_x) + fixed_mul(dy, step_y);
+            let mut v = pivot_v - fixed_mul(dx, step_y) + fixed_mul(dy, step_x);
+
+            for x in left..right {
+                if let Some((color, alpha)) = self.sample(u, v) {
+                    context.blend_pixel(Point::new(x, y), color, alpha);
+                }
+
+                u += step_x;
+                v -= step_y;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let radians = self.angle.to_radians();
+        let (left, top, right, bottom) = self.destination(
+            cosf(radians),
+            sinf(radians),
+            (i32::MIN, i32::MIN, i32::MAX, i32::MAX),
+        );
+
+        Rectangle::new(
+            Point::new(left, top),
+            Size::new((right - left) as u32, (bottom - top) as u32),
+        )
+    }
+}
+
+impl PartialEq for Sprite {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.image, other.image)
+            && self.x == other.x
+            && self.y == other.y
+            && self.pivot_x == other.pivot_x
+            && self.pivot_y == other.pivot_y
+            && self.angle == other.angle
+            && self.scale == other.scale
+            && self.sampling == other.sampling
+    }
+}
+
+fn fixed_mul(a: i32, b: i32) -> i32 {
+    ((a as i64 * b as i64) >> FRACTION_BITS) as i32
+}
+
+impl Display {
+    pub fn blit(&mut self, sprite: &Sprite) -> Result<DrawStats, DisplayError> {
+        self.draw(sprite)
+    }
+}
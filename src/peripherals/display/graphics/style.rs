@@ -0,0 +1,77 @@
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    primitives::{PrimitiveStyle, PrimitiveStyleBuilder},
+};
+
+use super::{Color, Fill};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stroke {
+    pub color: Color,
+    pub width: u16,
+}
+
+// How a shape is painted. The fill covers the interior and the stroke is centred on the outline,
+// drawn on top of the fill.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Style {
+    pub fill: Option<Fill>,
+    pub stroke: Option<Stroke>,
+}
+
+impl Style {
+    pub fn fill(fill: impl Into<Fill>) -> Self {
+        Self {
+            fill: Some(fill.into()),
+            stroke: None,
+        }
+    }
+
+    pub fn stroke(color: Color, width: u16) -> Self {
+        Self {
+            fill: None,
+            stroke: Some(Stroke { color, width }),
+        }
+    }
+
+    pub fn with_fill(self, fill: impl Into<Fill>) -> Self {
+        Self {
+            fill: Some(fill.into()),
+            ..self
+        }
+    }
+
+    pub fn with_stroke(self, color: Color, width: u16) -> Self {
+        Self {
+            stroke: Some(Stroke { color, width }),
+            ..self
+        }
+    }
+
+    // Solid fill colour, falling back to the stroke colour.
+    pub fn color(&self) -> Option<Color> {
+        match (self.fill, self.stroke) {
+            (Some(Fill::Solid(color)), _) => Some(color),
+            (_, Some(stroke)) => Some(stroke.color),
+            _ => None,
+        }
+    }
+
+    // Recolours whatever `color` reads from.
+    pub fn set_color(&mut self, color: Color) {
+        match (&mut self.fill, &mut self.stroke) {
+            (Some(Fill::Solid(fill)), _) => *fill = color,
+            (_, Some(stroke)) => stroke.color = color,
+            _ => {},
+        }
+    }
+}
+
+impl Stroke {
+    pub(super) fn primitive_style(&self) -> PrimitiveStyle<Rgb565> {
+        PrimitiveStyleBuilder::new()
+            .stroke_color(self.color.into())
+            .stroke_width(self.width as u32)
+            .build()
+    }
+}
@@ -1,42 +1,314 @@
 use alloc::string::String;
+use core::ptr;
 
 pub use embedded_graphics::text::Alignment;
 use embedded_graphics::{
-    mono_font::{MonoTextStyle, ascii::FONT_10X20},
-    prelude::{Drawable as EgDrawable, Point},
+    mono_font::{
+        MonoFont, MonoTextStyle,
+        ascii::{FONT_6X10, FONT_8X13, FONT_10X20},
+    },
+    pixelcolor::Rgb565,
+    prelude::{Dimensions, Drawable as EgDrawable, Point, Size},
+    primitives::Rectangle,
     text::{Baseline, LineHeight, Text as EgText, TextStyleBuilder},
 };
 use log::debug;
 
-use super::{Color, Graphic};
-use crate::peripherals::display::Display;
+use super::{Color, DrawContext, Graphic};
+use crate::{assets::Font, peripherals::display::error::DisplayError};
 
+// Line height as a percentage of the character height.
+const LINE_HEIGHT_PERCENT: u32 = 150;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontSize {
+    Small,
+    Medium,
+    #[default]
+    Large,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextMetrics {
+    pub width: u32,
+    pub height: u32,
+    pub lines: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Text {
     pub content: String,
     pub x: u16,
     pub y: u16,
     pub alignment: Alignment,
     pub color: Color,
+    pub size: FontSize,
 }
 
-impl Graphic for Text {
-    fn draw(&self, display: &mut Display) {
-        debug!(
-            "Drawing text '{}' at ({}, {}) with color {:?}",
-            self.content, self.x, self.y, self.color
-        );
+// Text set in a font converted from `assets/fonts/`, such as `assets::fonts::INTER_REGULAR_20`.
+// Placed like `Text`, with `y` on the middle of the first line.
+#[derive(Clone)]
+pub struct FontText {
+    pub content: String,
+    pub x: u16,
+    pub y: u16,
+    pub alignment: Alignment,
+    pub color: Color,
+    pub font: &'static Font,
+}
+
+impl FontSize {
+    pub(super) fn font(self) -> &'static MonoFont<'static> {
+        match self {
+            Self::Small => &FONT_6X10,
+            Self::Medium => &FONT_8X13,
+            Self::Large => &FONT_10X20,
+        }
+    }
+
+    pub fn smaller(self) -> Option<Self> {
+        match self {
+            Self::Small => None,
+            Self::Medium => Some(Self::Small),
+            Self::Large => Some(Self::Medium),
+        }
+    }
+
+    pub fn char_width(self) -> u32 {
+        self.font().character_size.width
+    }
 
+    pub fn char_height(self) -> u32 {
+        self.font().character_size.height
+    }
+
+    pub fn line_height(self) -> u32 {
+        self.char_height() * LINE_HEIGHT_PERCENT / 100
+    }
+
+    // Width of a single line of `chars` characters.
+    pub fn width_of(self, chars: usize) -> u32 {
+        let font = self.font();
+        let chars = chars as u32;
+
+        (chars * (font.character_size.width + font.character_spacing))
+            .saturating_sub(font.character_spacing)
+    }
+
+    // Number of characters that fit in a single line `width` pixels wide.
+    pub fn chars_in(self, width: u32) -> usize {
+        let font = self.font();
+        let advance = font.character_size.width + font.character_spacing;
+
+        ((width + font.character_spacing) / advance) as usize
+    }
+}
+
+impl Text {
+    fn styled(&self) -> EgText<'_, MonoTextStyle<'static, Rgb565>> {
         let position = Point::new(self.x as i32, self.y as i32);
         let color = self.color.into();
-        let character_style = MonoTextStyle::new(&FONT_10X20, color);
+        let character_style = MonoTextStyle::new(self.size.font(), color);
         let text_style = TextStyleBuilder::new()
-            .alignment(Alignment::Center)
+            .alignment(self.alignment)
             .baseline(Baseline::Middle)
-            .line_height(LineHeight::Percent(150))
+            .line_height(LineHeight::Percent(LINE_HEIGHT_PERCENT))
             .build();
 
-        let text = EgText::with_text_style(&self.content, position, character_style, text_style);
+        EgText::with_text_style(&self.content, position, character_style, text_style)
+    }
+
+    pub fn measure(&self) -> TextMetrics {
+        let size = self.bounds().size;
+
+        TextMetrics {
+            width: size.width,
+            height: size.height,
+            lines: self.content.split('\n').count() as u32,
+        }
+    }
+}
+
+impl Graphic for Text {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing text '{}' at ({}, {}) with color {:?}",
+            self.content, self.x, self.y, self.color
+        );
+
+        self.styled().draw(context)?;
+
+        Ok(())
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.styled().bounding_box()
+    }
+}
+
+impl FontText {
+    // Left edge of a line `width` pixels wide.
+    fn line_left(&self, width: u32) -> i32 {
+        let x = self.x as i32;
+
+        match self.alignment {
+            Alignment::Left => x,
+            Alignment::Center => x - width as i32 / 2,
+            Alignment::Right => x - width as i32,
+        }
+    }
+
+    // Baseline of the first line, which puts the middle between its ascent and descent on `y`.
+    fn baseline(&self) -> i32 {
+        self.y as i32 + (self.font.ascent + self.font.descent) as i32 / 2
+    }
+
+    pub fn measure(&self) -> TextMetrics {
+        let lines = self.content.split('\n').count() as u32;
+        let width = self
+            .content
+            .split('\n')
+            .map(|line| self.font.text_width(line))
+            .max()
+            .unwrap_or(0);
+        let height = (lines - 1) * self.font.line_height as u32
+            + (self.font.ascent - self.font.descent).max(0) as u32;
+
+        TextMetrics {
+            width,
+            height,
+            lines,
+        }
+    }
+}
+
+impl Graphic for FontText {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        debug!(
+            "Drawing text '{}' at ({}, {}) with color {:?} in a {}px font",
+            self.content, self.x, self.y, self.color, self.font.size
+        );
+
+        let color = self.color.to_rgb565();
+        let line_height = self.font.line_height as i32;
+
+        for (line, content) in self.content.split('\n').enumerate() {
+            let baseline = self.baseline() + line as i32 * line_height;
+            let mut pen = self.line_left(self.font.text_width(content));
+
+            for glyph in content
+                .chars()
+                .filter_map(|character| self.font.glyph(character))
+            {
+                let left = pen + glyph.x_offset as i32;
+                let top = baseline + glyph.y_offset as i32;
+
+                for y in 0..glyph.height {
+                    for x in 0..glyph.width {
+                        let point = Point::new(left + x as i32, top + y as i32);
+                        context.blend_pixel(point, color, self.font.coverage(glyph, x, y));
+                    }
+                }
+
+                pen += glyph.advance as i32;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let metrics = self.measure();
+        let left = self
+            .content
+            .split('\n')
+            .map(|line| self.line_left(self.font.text_width(line)))
+            .min()
+            .unwrap_or(self.x as i32);
+        let top = self.baseline() - self.font.ascent as i32;
+
+        Rectangle::new(
+            Point::new(left, top),
+            Size::new(metrics.width, metrics.height),
+        )
+    }
+}
+
+impl PartialEq for FontText {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.font, other.font)
+            && self.content == other.content
+            && self.x == other.x
+            && self.y == other.y
+            && self.alignment == other.alignment
+            && self.color == other.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::assets::{FontStyle, Glyph};
+
+    // Bitmap font with a single 4x6 glyph sitting on the baseline and advancing 5 pixels.
+    const FONT: Font = Font {
+        style: FontStyle::Bitmap,
+        size: 8,
+        ascent: 6,
+        descent: -2,
+        line_height: 10,
+        glyphs: &[Glyph {
+            character: 'A',
+            width: 4,
+            height: 6,
+            x_offset: 0,
+            y_offset: -6,
+            advance: 5,
+            offset: 0,
+        }],
+        data: &[0xFF, 0xFF, 0xFF],
+    };
+
+    fn text(content: &str, alignment: Alignment) -> FontText {
+        FontText {
+            content: content.to_string(),
+            x: 100,
+            y: 50,
+            alignment,
+            color: Color::WHITE,
+            font: &FONT,
+        }
+    }
+
+    #[test]
+    fn measure_lines() {
+        let metrics = text("AAA\nA", Alignment::Left).measure();
+
+        assert_eq!(metrics.width, 15);
+        assert_eq!(metrics.height, 18);
+        assert_eq!(metrics.lines, 2);
+    }
+
+    #[test]
+    fn bounds_follow_alignment() {
+        let cases = [
+            (Alignment::Left, 100),
+            (Alignment::Center, 93),
+            (Alignment::Right, 85),
+        ];
+
+        for (alignment, left) in cases {
+            let bounds = text("AAA", alignment).bounds();
+
+            assert_eq!(bounds.top_left, Point::new(left, 46), "{:?}", alignment);
+            assert_eq!(bounds.size, Size::new(15, 8));
+        }
+    }
 
-        let _ = text.draw(display);
+    #[test]
+    fn unknown_characters_are_skipped() {
+        assert_eq!(text("A?A", Alignment::Left).measure().width, 10);
     }
 }
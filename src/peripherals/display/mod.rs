@@ -3,18 +3,27 @@ mod config;
 pub mod error;
 pub mod graphics;
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 
 use embassy_time::{Duration, Timer};
-use esp_hal::gpio::Output;
 use log::{debug, info};
 
-use self::{config::CONFIG, error::DisplayError, graphics::Color};
-use crate::hardware::spi::SpiInterface;
+use self::{
+    config::CONFIG,
+    error::DisplayError,
+    graphics::{Color, DrawStats, Point, Rectangle, Size},
+};
+use crate::hardware::{Output, spi::SpiInterface};
 
 const DISPLAY_WIDTH: u16 = 240;
 const DISPLAY_HEIGHT: u16 = 240;
 const BUFFER_SIZE: usize = (DISPLAY_WIDTH as usize) * (DISPLAY_HEIGHT as usize) * 2;
+const REGION_CHUNK_SIZE: usize = 4096;
+
+const SCREEN: Rectangle = Rectangle::new(
+    Point::zero(),
+    Size::new(DISPLAY_WIDTH as u32, DISPLAY_HEIGHT as u32),
+);
 
 enum Operation {
     Command(u8),
@@ -27,6 +36,9 @@ pub struct Display {
     dc: Output<'static>,
     rst: Output<'static>,
     buffer: Box<[u8; BUFFER_SIZE]>,
+    stats: DrawStats,
+    // Area drawing and clearing are limited to.
+    clip: Rectangle,
 }
 
 impl Display {
@@ -36,6 +48,8 @@ impl Display {
             dc,
             rst,
             buffer: Box::new([0; BUFFER_SIZE]),
+            stats: DrawStats::default(),
+            clip: SCREEN,
         }
     }
 
@@ -170,15 +184,57 @@ impl Display {
         self.buffer[index + 1] = (color & 0xFF) as u8;
     }
 
+    pub fn get_pixel(&self, x: u16, y: u16) -> u16 {
+        let index = ((y as usize) * (DISPLAY_WIDTH as usize) + (x as usize)) * 2;
+
+        u16::from_be_bytes([self.buffer[index], self.buffer[index + 1]])
+    }
+
+    // Raw framebuffer, row by row, two big-endian bytes per RGB565 pixel.
+    pub fn frame(&self) -> &[u8] {
+        &self.buffer[..]
+    }
+
+    pub fn frame_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer[..]
+    }
+
+    // Fills the clip area, the whole screen unless `set_clip` was called.
     pub fn clear(&mut self, color: Color) {
         debug!("Setting background color: {:?}", color);
 
         let color_u16: u16 = color.into();
+        let (left, top) = (self.clip.top_left.x as usize, self.clip.top_left.y as usize);
+        let (width, height) = (
+            self.clip.size.width as usize,
+            self.clip.size.height as usize,
+        );
 
-        for i in 0..BUFFER_SIZE / 2 {
-            self.buffer[i * 2] = (color_u16 >> 8) as u8;
-            self.buffer[i * 2 + 1] = (color_u16 & 0xFF) as u8;
+        for row in self
+            .buffer
+            .chunks_exact_mut(DISPLAY_WIDTH as usize * 2)
+            .skip(top)
+            .take(height)
+        {
+            for pixel in row[left * 2..(left + width) * 2].chunks_exact_mut(2) {
+                pixel.copy_from_slice(&color_u16.to_be_bytes());
+            }
         }
+
+        self.stats = DrawStats::default();
+    }
+
+    // Limits drawing, clearing included, to `area` until `reset_clip` is called.
+    pub fn set_clip(&mut self, area: Rectangle) {
+        self.clip = area.intersection(&SCREEN);
+    }
+
+    pub fn reset_clip(&mut self) {
+        self.clip = SCREEN;
+    }
+
+    pub fn clip(&self) -> Rectangle {
+        self.clip
     }
 
     pub async fn render(&mut self) -> Result<(), DisplayError> {
@@ -192,4 +248,54 @@ impl Display {
 
         Ok(())
     }
+
+    pub async fn render_region(&mut self, area: Rectangle) -> Result<(), DisplayError> {
+        let area = area.intersection(&SCREEN);
+
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        debug!("Rendering region {:?} to display", area);
+
+        let (x1, y1) = (area.top_left.x as u16, area.top_left.y as u16);
+        let (x2, y2) = (bottom_right.x as u16, bottom_right.y as u16);
+
+        self.set_frame(x1, y1, x2, y2).await?;
+        self.dc.set_high();
+
+        let row_size = (DISPLAY_WIDTH as usize) * 2;
+        let start = (y1 as usize) * row_size;
+        let end = (y2 as usize + 1) * row_size;
+
+        // Full-width regions are contiguous in the framebuffer.
+        if x1 == 0 && x2 == DISPLAY_WIDTH - 1 {
+            self.spi.write(&self.buffer[start..end]).await?;
+            return Ok(());
+        }
+
+        let (left, right) = ((x1 as usize) * 2, (x2 as usize + 1) * 2);
+        let mut chunk = Vec::with_capacity(REGION_CHUNK_SIZE);
+
+        for row in self.buffer[start..end].chunks_exact(row_size) {
+            if chunk.len() + (right - left) > REGION_CHUNK_SIZE {
+                self.spi.write(&chunk).await?;
+                chunk.clear();
+            }
+
+            chunk.extend_from_slice(&row[left..right]);
+        }
+
+        self.spi.write(&chunk).await?;
+
+        Ok(())
+    }
+
+    pub async fn render_regions(&mut self, areas: &[Rectangle]) -> Result<(), DisplayError> {
+        for area in areas {
+            self.render_region(*area).await?;
+        }
+
+        Ok(())
+    }
 }
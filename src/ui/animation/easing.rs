@@ -0,0 +1,105 @@
+use core::f32::consts::PI;
+
+use libm::{cosf, expf};
+
+// Progress at which the spring last crosses its target. The swing past it that follows is too
+// small to see, so the spring rests on the target from there instead of jumping back at the end.
+const SPRING_SETTLED: f32 = 5.0 / 6.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    // Overshoots the target and settles with a damped oscillation.
+    Spring,
+    // Hits the target and bounces back a few times with decreasing height.
+    Bounce,
+}
+
+impl Easing {
+    // Maps linear progress in 0..=1 to eased progress. Spring may overshoot 1 while running but
+    // always starts at 0 and ends at 1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => t,
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => {
+                let inverse = 1.0 - t;
+                1.0 - inverse * inverse * inverse
+            },
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let inverse = -2.0 * t + 2.0;
+                    1.0 - inverse * inverse * inverse / 2.0
+                }
+            },
+            Self::Spring => {
+                if t >= SPRING_SETTLED {
+                    return 1.0;
+                }
+
+                1.0 - expf(-6.0 * t) * cosf(3.0 * PI * t)
+            },
+            Self::Bounce => bounce(t),
+        }
+    }
+}
+
+fn bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 6] = [
+        Easing::Linear,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::Spring,
+        Easing::Bounce,
+    ];
+
+    #[test]
+    fn ends_are_fixed() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn spring_settles_on_target() {
+        let overshoot = (0..=100)
+            .map(|step| Easing::Spring.apply(step as f32 / 100.0))
+            .fold(0.0, f32::max);
+        assert!(overshoot > 1.0);
+
+        for step in 84..=100 {
+            assert_eq!(Easing::Spring.apply(step as f32 / 100.0), 1.0);
+        }
+    }
+}
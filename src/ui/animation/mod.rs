@@ -0,0 +1,317 @@
+mod easing;
+mod tween;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use embassy_time::{Duration, Instant};
+use log::debug;
+
+pub use self::{
+    easing::Easing,
+    tween::{Animatable, Animated, Tween},
+};
+use super::{NodeId, Scene, Shape};
+use crate::peripherals::display::graphics::{Color, Fill, Point};
+
+// Frame interval while something on screen is moving, and while the UI is idle.
+pub const ACTIVE_FRAME_INTERVAL: Duration = Duration::from_millis(33);
+pub const IDLE_FRAME_INTERVAL: Duration = Duration::from_millis(1000);
+
+pub type AnimationId = usize;
+
+type Callback = Box<dyn FnOnce(&mut Scene)>;
+
+// Animatable property of a scene node and the value it should reach.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Property {
+    Position(Point),
+    Color(Color),
+    Angle(f32),
+    Opacity(u8),
+}
+
+enum Track {
+    Position(Tween<Point>),
+    Color(Tween<Color>),
+    Angle(Tween<f32>),
+    Opacity(Tween<u8>),
+}
+
+struct Animation {
+    node: NodeId,
+    track: Track,
+    on_complete: Option<Callback>,
+}
+
+// Runs property animations on scene nodes.
+#[derive(Default)]
+pub struct Animator {
+    animations: Vec<Option<Animation>>,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Animates a property of `node` from its current value. Any running animation of the same
+    // property on that node is replaced. Returns `None` if the node has no such property.
+    pub fn animate(
+        &mut self,
+        scene: &Scene,
+        node: NodeId,
+        target: Property,
+        now: Instant,
+        duration: Duration,
+        easing: Easing,
+    ) -> Option<AnimationId> {
+        let current = current_value(scene, node, &target)?;
+
+        let track = match (current, target) {
+            (Property::Position(from), Property::Position(to)) => {
+                Track::Position(Tween::new(from, to, now, duration, easing))
+            },
+            (Property::Color(from), Property::Color(to)) => {
+                Track::Color(Tween::new(from, to, now, duration, easing))
+            },
+            (Property::Angle(from), Property::Angle(to)) => {
+                Track::Angle(Tween::new(from, to, now, duration, easing))
+            },
+            (Property::Opacity(from), Property::Opacity(to)) => {
+                Track::Opacity(Tween::new(from, to, now, duration, easing))
+            },
+            _ => return None,
+        };
+
+        let replaced = self.animations.iter().position(|animation| {
+            animation
+                .as_ref()
+                .is_some_and(|animation| animation.node == node && animation.track.same(&track))
+        });
+
+        let animation = Some(Animation {
+            node,
+            track,
+            on_complete: None,
+        });
+
+        let id = match replaced.or_else(|| self.animations.iter().position(Option::is_none)) {
+            Some(id) => {
+                self.animations[id] = animation;
+                id
+            },
+            None => {
+                self.animations.push(animation);
+                self.animations.len() - 1
+            },
+        };
+
+        debug!("Animating node {} to {:?}", node, target);
+        Some(id)
+    }
+
+    // Runs `callback` once the animation reaches its target. Cancelled animations never call it.
+    pub fn on_complete<F>(&mut self, id: AnimationId, callback: F)
+    where
+        F: FnOnce(&mut Scene) + 'static,
+    {
+        if let Some(Some(animation)) = self.animations.get_mut(id) {
+            animation.on_complete = Some(Box::new(callback));
+        }
+    }
+
+    // Stops an animation, leaving the property at its current value.
+    pub fn cancel(&mut self, id: AnimationId) {
+        if let Some(slot) = self.animations.get_mut(id) {
+            *slot = None;
+        }
+    }
+
+    // Writes the current value of every animation into the scene and retires finished ones.
+    pub fn tick(&mut self, scene: &mut Scene, now: Instant) {
+        let mut completed = Vec::new();
+
+        for slot in self.animations.iter_mut() {
+            let Some(animation) = slot else {
+                continue;
+            };
+
+            apply(scene, animation.node, animation.track.value(now));
+
+            if animation.track.is_finished(now) {
+                completed.extend(slot.take().and_then(|animation| animation.on_complete));
+            }
+        }
+
+        for callback in completed {
+            callback(scene);
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.animations.iter().any(Option::is_some)
+    }
+}
+
+pub fn frame_interval(animating: bool) -> Duration {
+    if animating {
+        ACTIVE_FRAME_INTERVAL
+    } else {
+        IDLE_FRAME_INTERVAL
+    }
+}
+
+impl Track {
+    fn same(&self, other: &Track) -> bool {
+        core::mem::discriminant(self) == core::mem::discriminant(other)
+    }
+
+    fn value(&self, now: Instant) -> Property {
+        match self {
+            Self::Position(tween) => Property::Position(tween.value(now)),
+            Self::Color(tween) => Property::Color(tween.value(now)),
+            Self::Angle(tween) => Property::Angle(tween.value(now)),
+            Self::Opacity(tween) => Property::Opacity(tween.value(now)),
+        }
+    }
+
+    fn is_finished(&self, now: Instant) -> bool {
+        match self {
+            Self::Position(tween) => tween.is_finished(now),
+            Self::Color(tween) => tween.is_finished(now),
+            Self::Angle(tween) => tween.is_finished(now),
+            Self::Opacity(tween) => tween.is_finished(now),
+        }
+    }
+}
+
+fn current_value(scene: &Scene, node: NodeId, property: &Property) -> Option<Property> {
+    let shape = scene.get(node)?;
+
+    match property {
+        Property::Position(_) => shape.position().map(Property::Position),
+        Property::Color(_) => shape.color().map(Property::Color),
+        Property::Angle(_) => shape.angle().map(Property::Angle),
+        Property::Opacity(_) => scene.opacity(node).map(Property::Opacity),
+    }
+}
+
+fn apply(scene: &mut Scene, node: NodeId, value: Property) {
+    match value {
+        Property::Position(position) => scene.update(node, |shape| shape.set_position(position)),
+        Property::Color(color) => scene.update(node, |shape| shape.set_color(color)),
+        Property::Angle(angle) => scene.update(node, |shape| shape.set_angle(angle)),
+        Property::Opacity(opacity) => scene.set_opacity(node, opacity),
+    }
+}
+
+// Accessors used to animate shapes. The position of a shape is its anchor point (the centre of
+// circles, arcs and ellipses, the top left of rectangles, the first vertex of lines and polygons)
+// and moving it translates the whole shape. Its colour is its solid fill, or its stroke when it
+// has no solid fill, and its angle is the rotation of arcs and sprites.
+impl Shape {
+    fn position(&self) -> Option<Point> {
+        let point = |x: u16, y: u16| Some(Point::new(x as i32, y as i32));
+
+        match self {
+            Self::Circle(shape) => point(shape.x, shape.y),
+            Self::Rectangle(shape) => point(shape.x, shape.y),
+            Self::Arc(shape) => point(shape.x, shape.y),
+            Self::RoundedRectangle(shape) => point(shape.x, shape.y),
+            Self::Line(shape) => point(shape.x1, shape.y1),
+            Self::Polyline(shape) => shape.points.first().copied(),
+            Self::Polygon(shape) => shape.points.first().copied(),
+            Self::Triangle(shape) => point(shape.x1, shape.y1),
+            Self::Ellipse(shape) => point(shape.x, shape.y),
+            Self::Text(shape) => point(shape.x, shape.y),
+            Self::Icon(shape) => point(shape.x, shape.y),
+            Self::Sprite(shape) => point(shape.x, shape.y),
+        }
+    }
+
+    fn set_position(&mut self, position: Point) {
+        let Some(current) = self.position() else {
+            return;
+        };
+        let offset = position - current;
+        let shift = |x: &mut u16, y: &mut u16| {
+            *x = (*x as i32 + offset.x).clamp(0, u16::MAX as i32) as u16;
+            *y = (*y as i32 + offset.y).clamp(0, u16::MAX as i32) as u16;
+        };
+
+        match self {
+            Self::Circle(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Rectangle(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Arc(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::RoundedRectangle(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Line(shape) => {
+                shift(&mut shape.x1, &mut shape.y1);
+                shift(&mut shape.x2, &mut shape.y2);
+            },
+            Self::Polyline(shape) => shape.points.iter_mut().for_each(|p| *p += offset),
+            Self::Polygon(shape) => shape.points.iter_mut().for_each(|p| *p += offset),
+            Self::Triangle(shape) => {
+                shift(&mut shape.x1, &mut shape.y1);
+                shift(&mut shape.x2, &mut shape.y2);
+                shift(&mut shape.x3, &mut shape.y3);
+            },
+            Self::Ellipse(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Text(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Icon(shape) => shift(&mut shape.x, &mut shape.y),
+            Self::Sprite(shape) => shift(&mut shape.x, &mut shape.y),
+        }
+    }
+
+    fn color(&self) -> Option<Color> {
+        let solid = |fill: &Fill| match fill {
+            Fill::Solid(color) => Some(*color),
+            Fill::Gradient(_) => None,
+        };
+
+        match self {
+            Self::Circle(shape) => solid(&shape.fill),
+            Self::Rectangle(shape) => solid(&shape.fill),
+            Self::Arc(shape) => solid(&shape.fill),
+            Self::RoundedRectangle(shape) => shape.style.color(),
+            Self::Line(shape) => Some(shape.stroke.color),
+            Self::Polyline(shape) => Some(shape.stroke.color),
+            Self::Polygon(shape) => shape.style.color(),
+            Self::Triangle(shape) => shape.style.color(),
+            Self::Ellipse(shape) => shape.style.color(),
+            Self::Text(shape) => Some(shape.color),
+            Self::Icon(_) | Self::Sprite(_) => None,
+        }
+    }
+
+    fn set_color(&mut self, color: Color) {
+        match self {
+            Self::Circle(shape) => shape.fill = Fill::Solid(color),
+            Self::Rectangle(shape) => shape.fill = Fill::Solid(color),
+            Self::Arc(shape) => shape.fill = Fill::Solid(color),
+            Self::RoundedRectangle(shape) => shape.style.set_color(color),
+            Self::Line(shape) => shape.stroke.color = color,
+            Self::Polyline(shape) => shape.stroke.color = color,
+            Self::Polygon(shape) => shape.style.set_color(color),
+            Self::Triangle(shape) => shape.style.set_color(color),
+            Self::Ellipse(shape) => shape.style.set_color(color),
+            Self::Text(shape) => shape.color = color,
+            Self::Icon(_) | Self::Sprite(_) => {},
+        }
+    }
+
+    fn angle(&self) -> Option<f32> {
+        match self {
+            Self::Arc(shape) => Some(shape.start_angle),
+            Self::Sprite(shape) => Some(shape.angle),
+            _ => None,
+        }
+    }
+
+    fn set_angle(&mut self, angle: f32) {
+        match self {
+            Self::Arc(shape) => shape.start_angle = angle,
+            Self::Sprite(shape) => shape.angle = angle,
+            _ => {},
+        }
+    }
+}
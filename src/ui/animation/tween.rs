@@ -0,0 +1,149 @@
+use embassy_time::{Duration, Instant};
+use libm::roundf;
+
+use super::Easing;
+use crate::peripherals::display::graphics::{Color, Point};
+
+// Values that can be interpolated. `t` is eased progress, which may overshoot 0..=1.
+pub trait Animatable: Copy {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Animatable for u8 {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        roundf(f32::interpolate(from as f32, to as f32, t)).clamp(0.0, u8::MAX as f32) as u8
+    }
+}
+
+impl Animatable for u16 {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        roundf(f32::interpolate(from as f32, to as f32, t)).clamp(0.0, u16::MAX as f32) as u16
+    }
+}
+
+impl Animatable for Point {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        let x = f32::interpolate(from.x as f32, to.x as f32, t);
+        let y = f32::interpolate(from.y as f32, to.y as f32, t);
+
+        Point::new(roundf(x) as i32, roundf(y) as i32)
+    }
+}
+
+impl Animatable for Color {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        Color(
+            u8::interpolate(from.0, to.0, t),
+            u8::interpolate(from.1, to.1, t),
+            u8::interpolate(from.2, to.2, t),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tween<T: Animatable> {
+    pub from: T,
+    pub to: T,
+    pub start: Instant,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl<T: Animatable> Tween<T> {
+    pub fn new(from: T, to: T, start: Instant, duration: Duration, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            start,
+            duration,
+            easing,
+        }
+    }
+
+    // Linear progress at `now`, from 0 to 1.
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration == Duration::from_ticks(0) {
+            return 1.0;
+        }
+
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_micros() as f32 / self.duration.as_micros() as f32).min(1.0)
+    }
+
+    pub fn value(&self, now: Instant) -> T {
+        let progress = self.progress(now);
+        if progress >= 1.0 {
+            return self.to;
+        }
+
+        T::interpolate(self.from, self.to, self.easing.apply(progress))
+    }
+
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+}
+
+// Property that holds a value and can move towards a new one over time. Views keep these as
+// fields, read them with `value` while rendering and `poll` them on every tick to learn when to
+// redraw.
+pub struct Animated<T: Animatable> {
+    tween: Option<Tween<T>>,
+    value: T,
+}
+
+impl<T: Animatable> Animated<T> {
+    pub fn new(value: T) -> Self {
+        Self { tween: None, value }
+    }
+
+    // Starts moving from the current value, interrupting any running animation.
+    pub fn animate_to(&mut self, target: T, now: Instant, duration: Duration, easing: Easing) {
+        let from = self.value(now);
+
+        self.tween = Some(Tween::new(from, target, now, duration, easing));
+        self.value = target;
+    }
+
+    // Jumps to `value`, cancelling any running animation.
+    pub fn set(&mut self, value: T) {
+        self.tween = None;
+        self.value = value;
+    }
+
+    pub fn value(&self, now: Instant) -> T {
+        match &self.tween {
+            Some(tween) => tween.value(now),
+            None => self.value,
+        }
+    }
+
+    // Value the property is heading to, or its current value if idle.
+    pub fn target(&self) -> T {
+        self.value
+    }
+
+    pub fn is_animating(&self, now: Instant) -> bool {
+        self.tween.is_some_and(|tween| !tween.is_finished(now))
+    }
+
+    // Retires a finished animation. Returns whether the value changed since the previous poll,
+    // which includes the poll that finds the animation finished so it is drawn at rest.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let Some(tween) = self.tween else {
+            return false;
+        };
+
+        if tween.is_finished(now) {
+            self.tween = None;
+        }
+
+        true
+    }
+}
@@ -0,0 +1,57 @@
+use alloc::{boxed::Box, string::String};
+
+use super::{Dialog, Presentation, View};
+use crate::peripherals::display::graphics::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    // Knob turned by `delta` detents, positive clockwise, at `velocity` detents per second.
+    Rotate { delta: i32, velocity: f32 },
+    Press,
+    Release,
+    LongPress,
+    DoubleClick,
+}
+
+#[derive(Debug)]
+pub enum ViewAction {
+    // The event was ignored.
+    None,
+    // The view changed and should be drawn again.
+    Redraw,
+    Navigate(Navigation),
+    // Push a view on top of the navigation stack.
+    Open(Box<dyn View>, Presentation),
+    // Ask for confirmation before carrying out the dialog's action.
+    Confirm(Box<Dialog>),
+    Command(Command),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Navigation {
+    Next,
+    Previous,
+    To(usize),
+    // Close the view on top of the navigation stack.
+    Back,
+}
+
+// Request for the outside world, addressed to the entity a view controls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Command {
+    pub target: String,
+    pub action: CommandAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandAction {
+    Toggle,
+    TurnOn,
+    TurnOff,
+    Set(i32),
+    Step(i32),
+    // Choose one of the target's named options, such as a mode.
+    Select(&'static str),
+    SetColor(Color),
+    Trigger,
+}
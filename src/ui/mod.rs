@@ -1,14 +1,115 @@
+pub mod animation;
+mod input;
+mod navigator;
+mod overlay;
+mod scene;
+mod theme;
+mod transition;
 mod views;
+mod widgets;
 
 use alloc::{boxed::Box, vec::Vec};
+use core::mem;
 
-pub use views::{LightView, View};
+use embassy_time::{Duration, Instant};
+use embedded_graphics::prelude::Dimensions;
+use log::debug;
 
-use crate::peripherals::display::Display;
+use self::{
+    animation::{Animator, Easing},
+    overlay::Overlay,
+    transition::ActiveTransition,
+};
+pub use self::{
+    input::{Command, CommandAction, InputEvent, Navigation, ViewAction},
+    navigator::{EdgePolicy, Navigator, Presentation},
+    overlay::{Dialog, Toast, ToastLevel},
+    scene::{NodeId, Scene, Shape},
+    theme::Theme,
+    transition::Transition,
+    views::{
+        Binding, ClockFace, ClockView, ColorView, HvacMode, LightView, MediaView, MenuItem,
+        MenuView, NumberView, ThermostatView, View,
+    },
+    widgets::{PickerEvent, StepMode, ValuePicker},
+};
+use crate::{
+    entity::Entity,
+    peripherals::display::{Display, error::DisplayError, graphics::Rectangle},
+};
 
-#[derive(Default)]
+pub const DEFAULT_TRANSITION_DURATION: Duration = Duration::from_millis(300);
+pub const DEFAULT_TRANSITION_EASING: Easing = Easing::CubicInOut;
+const DEFAULT_SCREENSAVER_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct Layer {
+    view: Box<dyn View>,
+    presentation: Presentation,
+}
+
+// Screen shown before the last screen change, drawn again while transitioning away from it. It is
+// the root view at `root` with the first `depth` layers of the stack, then the layers the change
+// closed, and the screensaver on top if the change dismissed it.
+struct Outgoing {
+    root: usize,
+    depth: usize,
+    closed: Vec<Layer>,
+    screensaver: bool,
+}
+
+// Owns the views and decides which of them are shown. The root views form a list moved through
+// with the navigator, and views opened on top of them are kept on a stack and closed with `Back`.
+// Only the view on top, the focused one, receives input and ticks.
 pub struct ViewManager {
     views: Vec<Box<dyn View>>,
+    navigator: Navigator,
+    stack: Vec<Layer>,
+    theme: Theme,
+    // Whether the focused view has been entered, which happens on the first update.
+    started: bool,
+    // Set when something outside the current views changed and the next frame must be drawn.
+    dirty: bool,
+    // Set when the shown screen changed and the next frame should start a transition.
+    screen_changed: bool,
+    transition: Transition,
+    transition_duration: Duration,
+    transition_easing: Easing,
+    active_transition: Option<ActiveTransition>,
+    outgoing: Option<Outgoing>,
+    // Shown after `screensaver_timeout` without input and closed by turning the knob. It is kept
+    // here while hidden and moved onto the stack, at `screensaver_depth`, while shown.
+    screensaver: Option<Box<dyn View>>,
+    screensaver_timeout: Duration,
+    screensaver_depth: Option<usize>,
+    idle: Duration,
+    overlay: Overlay,
+    // Animations on the scene of the focused view, dropped when focus moves.
+    animator: Animator,
+}
+
+impl Default for ViewManager {
+    fn default() -> Self {
+        Self {
+            views: Vec::new(),
+            navigator: Navigator::default(),
+            stack: Vec::new(),
+            theme: Theme::default(),
+            started: false,
+            dirty: true,
+            screen_changed: false,
+            transition: Transition::default(),
+            transition_duration: DEFAULT_TRANSITION_DURATION,
+            transition_easing: DEFAULT_TRANSITION_EASING,
+            active_transition: None,
+            outgoing: None,
+            screensaver: None,
+            screensaver_timeout: DEFAULT_SCREENSAVER_TIMEOUT,
+            screensaver_depth: None,
+            idle: Duration::from_ticks(0),
+            overlay: Overlay::default(),
+            animator: Animator::new(),
+        }
+    }
 }
 
 impl ViewManager {
@@ -16,14 +117,455 @@ impl ViewManager {
         Self::default()
     }
 
+    // Switches the theme used by every view from the next frame on.
+    pub fn set_theme(&mut self, theme: Theme) {
+        debug!("Switching theme to {:?}", theme);
+        self.theme = theme;
+        self.dirty = true;
+    }
+
+    // Forces the shown views to be drawn on the next call to `update`.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    // Transition played whenever the shown screen changes.
+    pub fn set_transition(&mut self, transition: Transition, duration: Duration, easing: Easing) {
+        self.transition = transition;
+        self.transition_duration = duration;
+        self.transition_easing = easing;
+    }
+
+    pub fn set_edge_policy(&mut self, policy: EdgePolicy) {
+        self.navigator.policy = policy;
+    }
+
+    // View shown on top of everything once no input arrived for `timeout`.
+    pub fn set_screensaver(&mut self, view: Box<dyn View>, timeout: Duration) {
+        self.dismiss_screensaver();
+        self.screensaver = Some(view);
+        self.screensaver_timeout = timeout;
+    }
+
+    pub fn is_screensaver_shown(&self) -> bool {
+        self.screensaver_depth.is_some()
+    }
+
+    // Shows a message over the current view once the toasts before it have timed out.
+    pub fn show_toast(&mut self, toast: Toast) {
+        self.overlay.show_toast(toast);
+    }
+
+    // Shows a dialog over the current view, taking input until it is answered.
+    pub fn show_dialog(&mut self, dialog: Dialog) {
+        self.idle = Duration::from_ticks(0);
+        self.dismiss_screensaver();
+        self.overlay.show_dialog(dialog);
+    }
+
+    pub fn has_dialog(&self) -> bool {
+        self.overlay.has_dialog()
+    }
+
     pub fn add(&mut self, view: Box<dyn View>) {
         self.views.push(view);
+        self.navigator.set_len(self.views.len());
+    }
+
+    // Carries out a navigation and returns whether the focused view changed. Moving between root
+    // views closes everything opened on top of them.
+    pub fn navigate(&mut self, navigation: Navigation) -> bool {
+        if navigation == Navigation::Back {
+            return self.pop();
+        }
+
+        let Some(index) = self.navigator.resolve(navigation) else {
+            return false;
+        };
+        if index == self.navigator.index() && self.stack.is_empty() {
+            return false;
+        }
+
+        debug!(
+            "Navigating from view {} to {}",
+            self.navigator.index(),
+            index
+        );
+
+        let screensaver = self.dismiss_screensaver();
+        self.exit_focused();
+        let closed = mem::take(&mut self.stack);
+        self.leave_screen(0, closed, screensaver);
+        self.navigator.navigate(navigation);
+        self.enter_focused();
+
+        true
+    }
+
+    // Opens a view on top of the shown ones and gives it focus.
+    pub fn push(&mut self, view: Box<dyn View>, presentation: Presentation) {
+        debug!(
+            "Opening {:?} view at depth {}",
+            presentation,
+            self.stack.len() + 1
+        );
+
+        if presentation == Presentation::Screen {
+            self.leave_screen(self.stack.len(), Vec::new(), false);
+        }
+
+        self.exit_focused();
+        self.stack.push(Layer { view, presentation });
+        self.enter_focused();
+
+        self.dirty = true;
+    }
+
+    // Closes the view on top of the stack, returns focus to the one below it and returns whether
+    // there was a view to close.
+    pub fn pop(&mut self) -> bool {
+        if self.stack.is_empty() {
+            return false;
+        }
+
+        if self.screensaver_depth == Some(self.stack.len() - 1) {
+            return self.dismiss_screensaver();
+        }
+
+        self.exit_focused();
+        let Some(layer) = self.stack.pop() else {
+            return false;
+        };
+        self.enter_focused();
+
+        debug!(
+            "Closed {:?} view, depth is now {}",
+            layer.presentation,
+            self.stack.len()
+        );
+
+        if layer.presentation == Presentation::Screen {
+            self.leave_screen(self.stack.len(), Vec::from([layer]), false);
+        }
+
+        self.dirty = true;
+        true
+    }
+
+    // Keeps what is shown until now for the transition to the next screen, which starts on the
+    // next update.
+    fn leave_screen(&mut self, depth: usize, closed: Vec<Layer>, screensaver: bool) {
+        self.outgoing = Some(Outgoing {
+            root: self.navigator.index(),
+            depth,
+            closed,
+            screensaver,
+        });
+        self.screen_changed = true;
+    }
+
+    // Draws the shown views if anything changed since the previous call and returns the areas of
+    // the framebuffer that were updated. When a view that draws through a scene is all that is
+    // shown, only the areas of its scene that changed are redrawn, so the framebuffer must still
+    // hold the previous frame.
+    pub fn update(&mut self, display: &mut Display) -> Result<Vec<Rectangle>, DisplayError> {
+        let now = Instant::now();
+
+        if !self.started {
+            self.started = true;
+            self.enter_focused();
+        }
+
+        if self.screen_changed {
+            self.screen_changed = false;
+            self.dirty = true;
+            self.active_transition = ActiveTransition::start(
+                self.transition,
+                display,
+                now,
+                self.transition_duration,
+                self.transition_easing,
+            );
+
+            if self.active_transition.is_none() {
+                self.outgoing = None;
+            }
+        }
+
+        self.animate(now);
+
+        let changed = self.overlay.needs_redraw() || self.visible().any(|view| view.needs_redraw());
+        if !self.dirty && !changed && self.active_transition.is_none() {
+            return Ok(Vec::new());
+        }
+
+        if let Some(regions) = self.draw_scene(display)? {
+            return Ok(regions);
+        }
+
+        self.draw(display)?;
+        self.dirty = false;
+
+        for view in self.visible_mut() {
+            view.mark_clean();
+        }
+        self.overlay.mark_clean();
+
+        if let Some(mut transition) = self.active_transition.take() {
+            transition.compose(display, now, |display| self.draw_outgoing(display))?;
+
+            if transition.is_finished(now) {
+                self.outgoing = None;
+            } else {
+                self.active_transition = Some(transition);
+            }
+        }
+
+        Ok(Vec::from([display.bounding_box()]))
+    }
+
+    // Advances the focused view's timers by `dt`, the time since the previous tick.
+    pub fn tick(&mut self, dt: Duration) {
+        self.idle += dt;
+        if self.idle >= self.screensaver_timeout && !self.overlay.has_dialog() {
+            self.show_screensaver();
+        }
+
+        self.overlay.tick(dt);
+
+        if let Some(view) = self.focused_mut() {
+            view.tick(dt);
+        }
+    }
+
+    // Redraws only the areas that changed when the focused view draws through a scene, is all
+    // that is shown, and nothing calls for the whole screen to be redrawn.
+    fn draw_scene(
+        &mut self,
+        display: &mut Display,
+    ) -> Result<Option<Vec<Rectangle>>, DisplayError> {
+        if self.dirty
+            || self.active_transition.is_some()
+            || self.overlay.is_shown()
+            || self.overlay.needs_redraw()
+            || self.visible().count() != 1
+        {
+            return Ok(None);
+        }
+
+        let theme = self.theme;
+        let Some(view) = self.focused_mut() else {
+            return Ok(None);
+        };
+        let Some(scene) = view.scene(&theme) else {
+            return Ok(None);
+        };
+
+        let regions = scene.render(display)?;
+        view.mark_clean();
+
+        Ok(Some(regions))
     }
 
-    pub fn select(&self, index: usize, display: &mut Display) {
-        if let Some(view) = self.views.get(index) {
-            view.render(display);
+    fn show_screensaver(&mut self) {
+        let Some(view) = self.screensaver.take() else {
+            return;
+        };
+
+        debug!(
+            "No input for {} ms, showing screensaver",
+            self.idle.as_millis()
+        );
+
+        self.screensaver_depth = Some(self.stack.len());
+        self.push(view, Presentation::Screen);
+    }
+
+    // Takes the screensaver off the stack, along with focus if it had it, and returns whether it
+    // was shown.
+    fn dismiss_screensaver(&mut self) -> bool {
+        let Some(depth) = self.screensaver_depth.take() else {
+            return false;
+        };
+
+        debug!("Dismissing screensaver");
+
+        let focused = depth == self.stack.len() - 1;
+        if focused {
+            self.exit_focused();
+        }
+
+        let layer = self.stack.remove(depth);
+        self.screensaver = Some(layer.view);
+
+        if focused {
+            self.enter_focused();
+        }
+
+        self.leave_screen(depth, Vec::new(), true);
+        self.dirty = true;
+
+        true
+    }
+
+    // Lets the focused view start animations on its scene and moves the running ones on to `now`.
+    fn animate(&mut self, now: Instant) {
+        let theme = self.theme;
+        // Taken out while the focused view is borrowed from the manager.
+        let mut animator = mem::take(&mut self.animator);
+
+        if let Some(view) = self.focused_mut()
+            && view.scene(&theme).is_some()
+        {
+            view.animate(&mut animator, now);
+
+            if let Some(scene) = view.scene(&theme) {
+                animator.tick(scene, now);
+            }
         }
+
+        self.animator = animator;
+    }
+
+    fn draw(&mut self, display: &mut Display) -> Result<(), DisplayError> {
+        display.clear(self.theme.background);
+
+        let theme = self.theme;
+        for view in self.visible_mut() {
+            // The scene is drawn by the view, so it holds what is on screen from now on.
+            if let Some(scene) = view.scene(&theme) {
+                scene.mark_drawn();
+            }
+
+            view.render(display, &theme)?;
+        }
+
+        self.overlay.render(display, &self.theme)?;
+
+        let stats = display.stats();
+        if stats.clipped > 0 {
+            debug!(
+                "Views drew {} pixels and clipped {}",
+                stats.drawn, stats.clipped
+            );
+        }
+
+        Ok(())
+    }
+
+    // Draws the screen being transitioned away from.
+    fn draw_outgoing(&self, display: &mut Display) -> Result<(), DisplayError> {
+        display.clear(self.theme.background);
+
+        let Some(outgoing) = &self.outgoing else {
+            return Ok(());
+        };
+
+        let held = &self.stack[..outgoing.depth.min(self.stack.len())];
+        let screensaver = self
+            .screensaver
+            .iter()
+            .filter(|_| outgoing.screensaver)
+            .map(|view| (view.as_ref(), Presentation::Screen));
+        let layers: Vec<(&dyn View, Presentation)> = held
+            .iter()
+            .chain(&outgoing.closed)
+            .map(|layer| (layer.view.as_ref(), layer.presentation))
+            .chain(screensaver)
+            .collect();
+
+        let base = layers
+            .iter()
+            .rposition(|(_, presentation)| *presentation == Presentation::Screen);
+        let root = match base {
+            Some(_) => None,
+            None => self.views.get(outgoing.root).map(|view| view.as_ref()),
+        };
+
+        for view in root
+            .into_iter()
+            .chain(layers[base.unwrap_or(0)..].iter().map(|(view, _)| *view))
+        {
+            view.render(display, &self.theme)?;
+        }
+
+        self.overlay.render(display, &self.theme)
+    }
+
+    // Position in the stack of the topmost view drawn, along with the modals above it. `None`
+    // means the current root view is drawn.
+    fn base_layer(&self) -> Option<usize> {
+        self.stack
+            .iter()
+            .rposition(|layer| layer.presentation == Presentation::Screen)
+    }
+
+    // Views drawn on screen, from the bottom up.
+    fn visible(&self) -> impl Iterator<Item = &dyn View> {
+        let base = self.base_layer();
+        let root = match base {
+            Some(_) => None,
+            None => self.views.get(self.navigator.index()),
+        };
+
+        root.into_iter()
+            .chain(
+                self.stack[base.unwrap_or(0)..]
+                    .iter()
+                    .map(|layer| &layer.view),
+            )
+            .map(|view| view.as_ref())
+    }
+
+    fn visible_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn View>> {
+        let base = self.base_layer();
+        let root = match base {
+            Some(_) => None,
+            None => self.views.get_mut(self.navigator.index()),
+        };
+
+        root.into_iter().chain(
+            self.stack[base.unwrap_or(0)..]
+                .iter_mut()
+                .map(|layer| &mut layer.view),
+        )
+    }
+
+    fn focused_mut(&mut self) -> Option<&mut Box<dyn View>> {
+        match self.stack.last_mut() {
+            Some(layer) => Some(&mut layer.view),
+            None => self.views.get_mut(self.navigator.index()),
+        }
+    }
+
+    fn enter_focused(&mut self) {
+        self.animator = Animator::new();
+
+        if self.started
+            && let Some(view) = self.focused_mut()
+        {
+            view.on_enter();
+        }
+    }
+
+    fn exit_focused(&mut self) {
+        if self.started
+            && let Some(view) = self.focused_mut()
+        {
+            view.on_exit();
+        }
+    }
+
+    // Whether a shown view is animating or a transition is playing.
+    pub fn is_animating(&self) -> bool {
+        self.active_transition.is_some()
+            || self.screen_changed
+            || self.animator.is_active()
+            || self.visible().any(|view| view.is_animating())
     }
 
     pub fn len(&self) -> usize {
@@ -33,4 +575,82 @@ impl ViewManager {
     pub fn is_empty(&self) -> bool {
         self.views.is_empty()
     }
+
+    // Index of the current root view.
+    pub fn current(&self) -> usize {
+        self.navigator.index()
+    }
+
+    // Number of views opened on top of the current root view.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    // Passes an input event to the focused view, or to the dialog shown over it, and carries out
+    // the action returned. Commands are handed back to the caller. Turning the knob dismisses the
+    // screensaver and goes no further, while other input reaches the screensaver itself.
+    pub fn dispatch(&mut self, event: InputEvent) -> Option<Command> {
+        self.idle = Duration::from_ticks(0);
+        if matches!(event, InputEvent::Rotate { .. }) && self.dismiss_screensaver() {
+            return None;
+        }
+
+        let action = match self.overlay.handle_event(event) {
+            Some(action) => action,
+            None => self.focused_mut()?.handle_event(event),
+        };
+        debug!("Handled {:?} with {:?}", event, action);
+
+        self.perform(action)
+    }
+
+    // Passes the new state of an entity to every view bound to it, shown or not.
+    pub fn notify(&mut self, entity: &Entity) {
+        self.notify_views(entity, false);
+    }
+
+    // Like `notify`, but leaves out the focused view, which caused the change through input and
+    // already shows it.
+    pub fn notify_others(&mut self, entity: &Entity) {
+        self.notify_views(entity, true);
+    }
+
+    fn notify_views(&mut self, entity: &Entity, skip_focused: bool) {
+        let focused_root = (skip_focused && self.stack.is_empty()).then(|| self.navigator.index());
+        let focused_layer = self.stack.len().checked_sub(1).filter(|_| skip_focused);
+
+        let roots = self
+            .views
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != focused_root)
+            .map(|(_, view)| view);
+        let layers = self
+            .stack
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != focused_layer)
+            .map(|(_, layer)| &mut layer.view);
+
+        for view in roots.chain(layers).chain(self.screensaver.iter_mut()) {
+            if view.entity() == Some(entity.id.as_str()) {
+                view.on_entity_changed(entity);
+            }
+        }
+    }
+
+    fn perform(&mut self, action: ViewAction) -> Option<Command> {
+        match action {
+            ViewAction::None => {},
+            ViewAction::Redraw => self.dirty = true,
+            ViewAction::Navigate(navigation) => {
+                self.navigate(navigation);
+            },
+            ViewAction::Open(view, presentation) => self.push(view, presentation),
+            ViewAction::Confirm(dialog) => self.show_dialog(*dialog),
+            ViewAction::Command(command) => return Some(command),
+        }
+
+        None
+    }
 }
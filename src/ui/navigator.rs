@@ -0,0 +1,221 @@
+use super::Navigation;
+
+// What happens when moving past the first or last view.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgePolicy {
+    #[default]
+    Wrap,
+    Clamp,
+}
+
+// How a view pushed on top of the navigation stack is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Presentation {
+    // Replaces everything below it, like a sub-menu.
+    Screen,
+    // Drawn over the views below it, which stay visible but stop receiving input.
+    Modal,
+}
+
+// Position within a list of `len` views.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Navigator {
+    pub policy: EdgePolicy,
+    index: usize,
+    len: usize,
+}
+
+impl Navigator {
+    pub fn new(policy: EdgePolicy) -> Self {
+        Self {
+            policy,
+            index: 0,
+            len: 0,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Updates the number of views, keeping the index within them.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.index = self.index.min(len.saturating_sub(1));
+    }
+
+    // Index reached by `navigation`. `Back` is not a move within the list and resolves to `None`,
+    // as do moves when there are no views and indices past the end.
+    pub fn resolve(&self, navigation: Navigation) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let last = self.len - 1;
+
+        match (navigation, self.policy) {
+            (Navigation::Next, EdgePolicy::Wrap) => Some(if self.index == last {
+                0
+            } else {
+                self.index + 1
+            }),
+            (Navigation::Next, EdgePolicy::Clamp) => Some((self.index + 1).min(last)),
+            (Navigation::Previous, EdgePolicy::Wrap) => Some(if self.index == 0 {
+                last
+            } else {
+                self.index - 1
+            }),
+            (Navigation::Previous, EdgePolicy::Clamp) => Some(self.index.saturating_sub(1)),
+            (Navigation::To(index), _) => (index <= last).then_some(index),
+            (Navigation::Back, _) => None,
+        }
+    }
+
+    // Moves according to `navigation` and returns whether the index changed.
+    pub fn navigate(&mut self, navigation: Navigation) -> bool {
+        match self.resolve(navigation) {
+            Some(index) if index != self.index => {
+                self.index = index;
+                true
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        string::{String, ToString},
+    };
+
+    use super::*;
+    use crate::{
+        peripherals::display::{Display, error::DisplayError},
+        ui::{Command, CommandAction, InputEvent, Theme, View, ViewAction, ViewManager},
+    };
+
+    // View that sends its name when pressed, to tell which one has focus.
+    struct Named(&'static str);
+
+    impl View for Named {
+        fn new(_name: &str) -> Self {
+            unreachable!()
+        }
+
+        fn render(&self, _display: &mut Display, _theme: &Theme) -> Result<(), DisplayError> {
+            Ok(())
+        }
+
+        fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+            match event {
+                InputEvent::Press => ViewAction::Command(Command {
+                    target: self.0.to_string(),
+                    action: CommandAction::Trigger,
+                }),
+                _ => ViewAction::None,
+            }
+        }
+    }
+
+    fn focused(manager: &mut ViewManager) -> Option<String> {
+        manager
+            .dispatch(InputEvent::Press)
+            .map(|command| command.target)
+    }
+
+    fn navigator(policy: EdgePolicy, len: usize, index: usize) -> Navigator {
+        let mut navigator = Navigator::new(policy);
+        navigator.set_len(len);
+        navigator.navigate(Navigation::To(index));
+        navigator
+    }
+
+    #[test]
+    fn wrap_goes_around_both_edges() {
+        let first = navigator(EdgePolicy::Wrap, 3, 0);
+        assert_eq!(first.resolve(Navigation::Previous), Some(2));
+        assert_eq!(first.resolve(Navigation::Next), Some(1));
+
+        let last = navigator(EdgePolicy::Wrap, 3, 2);
+        assert_eq!(last.resolve(Navigation::Next), Some(0));
+        assert_eq!(last.resolve(Navigation::Previous), Some(1));
+    }
+
+    #[test]
+    fn clamp_stops_at_both_edges() {
+        let mut first = navigator(EdgePolicy::Clamp, 3, 0);
+        assert_eq!(first.resolve(Navigation::Previous), Some(0));
+        assert!(!first.navigate(Navigation::Previous));
+
+        let mut last = navigator(EdgePolicy::Clamp, 3, 2);
+        assert_eq!(last.resolve(Navigation::Next), Some(2));
+        assert!(!last.navigate(Navigation::Next));
+    }
+
+    #[test]
+    fn to_past_the_end_resolves_to_none() {
+        let navigator = navigator(EdgePolicy::Wrap, 3, 1);
+
+        assert_eq!(navigator.resolve(Navigation::To(2)), Some(2));
+        assert_eq!(navigator.resolve(Navigation::To(3)), None);
+        assert_eq!(navigator.resolve(Navigation::To(usize::MAX)), None);
+    }
+
+    #[test]
+    fn empty_and_back_resolve_to_none() {
+        let empty = Navigator::new(EdgePolicy::Wrap);
+        assert_eq!(empty.resolve(Navigation::Next), None);
+        assert_eq!(empty.resolve(Navigation::To(0)), None);
+
+        let navigator = navigator(EdgePolicy::Wrap, 3, 1);
+        assert_eq!(navigator.resolve(Navigation::Back), None);
+    }
+
+    #[test]
+    fn set_len_keeps_index_within_views() {
+        let mut navigator = navigator(EdgePolicy::Wrap, 5, 4);
+
+        navigator.set_len(2);
+        assert_eq!(navigator.index(), 1);
+        assert_eq!(navigator.resolve(Navigation::Next), Some(0));
+
+        navigator.set_len(0);
+        assert_eq!(navigator.index(), 0);
+        assert!(navigator.is_empty());
+    }
+
+    #[test]
+    fn stack_closes_views_in_reverse_order() {
+        let mut manager = ViewManager::new();
+        manager.add(Box::new(Named("root")));
+
+        manager.push(Box::new(Named("menu")), Presentation::Screen);
+        manager.push(Box::new(Named("modal")), Presentation::Modal);
+        manager.push(Box::new(Named("dialog")), Presentation::Modal);
+        assert_eq!(manager.depth(), 3);
+        assert_eq!(focused(&mut manager).as_deref(), Some("dialog"));
+
+        assert!(manager.navigate(Navigation::Back));
+        assert_eq!(manager.depth(), 2);
+        assert_eq!(focused(&mut manager).as_deref(), Some("modal"));
+
+        assert!(manager.pop());
+        assert_eq!(focused(&mut manager).as_deref(), Some("menu"));
+        assert!(manager.pop());
+        assert_eq!(focused(&mut manager).as_deref(), Some("root"));
+
+        assert!(!manager.pop());
+        assert!(!manager.navigate(Navigation::Back));
+        assert_eq!(manager.depth(), 0);
+    }
+}
@@ -0,0 +1,330 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use embassy_time::Duration;
+use log::debug;
+
+use super::{InputEvent, Theme, ViewAction, views::dial};
+use crate::peripherals::display::{
+    Display,
+    error::DisplayError,
+    graphics::{Alignment, DrawContext, FilledCircle, Overflow, RoundedRectangle, Style, Text},
+};
+
+const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(3);
+// Toasts waiting beyond this are dropped, oldest first, so a burst does not keep the screen busy.
+const MAX_QUEUED_TOASTS: usize = 4;
+
+const TOAST_Y: u16 = 196;
+const TOAST_PADDING: u16 = 12;
+
+// Opacity of the background laid over the views behind a dialog.
+const DIALOG_DIM: u8 = 208;
+const BUTTON_Y: u16 = 160;
+const BUTTON_WIDTH: u16 = 72;
+const BUTTON_HEIGHT: u16 = 32;
+const BUTTON_SPACING: u16 = 80;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ToastLevel {
+    #[default]
+    Info,
+    Error,
+}
+
+// Short message shown at the bottom of the panel for a while, without taking input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn info(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            level: ToastLevel::Info,
+            duration: DEFAULT_TOAST_DURATION,
+        }
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self {
+            level: ToastLevel::Error,
+            ..Self::info(message)
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Button {
+    #[default]
+    Cancel,
+    Confirm,
+}
+
+// Question answered with confirm or cancel. Turning the knob moves between the buttons, cancel on
+// the left and selected at first, and pressing picks one. Confirming carries out `on_confirm`.
+#[derive(Debug)]
+pub struct Dialog {
+    message: String,
+    confirm_label: String,
+    cancel_label: String,
+    on_confirm: ViewAction,
+    selected: Button,
+}
+
+impl Dialog {
+    pub fn new(message: &str, on_confirm: ViewAction) -> Self {
+        Self {
+            message: message.to_string(),
+            confirm_label: String::from("OK"),
+            cancel_label: String::from("Cancel"),
+            on_confirm,
+            selected: Button::default(),
+        }
+    }
+
+    pub fn with_labels(mut self, confirm: &str, cancel: &str) -> Self {
+        self.confirm_label = confirm.to_string();
+        self.cancel_label = cancel.to_string();
+        self
+    }
+
+    fn draw_button(
+        &self,
+        display: &mut Display,
+        theme: &Theme,
+        button: Button,
+        x: u16,
+    ) -> Result<(), DisplayError> {
+        let label = match button {
+            Button::Cancel => &self.cancel_label,
+            Button::Confirm => &self.confirm_label,
+        };
+
+        let (style, text_color) = if button == self.selected {
+            (Style::fill(theme.accent), theme.background)
+        } else {
+            (
+                Style::stroke(theme.muted, theme.stroke_width),
+                theme.foreground,
+            )
+        };
+
+        display.draw(&RoundedRectangle {
+            x: x - BUTTON_WIDTH / 2,
+            y: BUTTON_Y - BUTTON_HEIGHT / 2,
+            width: BUTTON_WIDTH,
+            height: BUTTON_HEIGHT,
+            radius: BUTTON_HEIGHT / 2,
+            style,
+        })?;
+
+        display.draw(&Text {
+            content: label.clone(),
+            x,
+            y: BUTTON_Y,
+            alignment: Alignment::Center,
+            color: text_color,
+            size: theme.caption_font,
+        })?;
+
+        Ok(())
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        DrawContext::new(display)
+            .with_opacity(DIALOG_DIM)
+            .draw(&FilledCircle {
+                x: dial::CENTER,
+                y: dial::CENTER,
+                diameter: 2 * dial::CENTER,
+                fill: theme.background.into(),
+            })?;
+
+        let message = Text {
+            content: self.message.clone(),
+            x: dial::CENTER,
+            y: 100,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.body_font,
+        }
+        .fit_to_circle(Overflow::Wrap, 30);
+
+        display.draw(&message)?;
+
+        self.draw_button(
+            display,
+            theme,
+            Button::Cancel,
+            dial::CENTER - BUTTON_SPACING / 2,
+        )?;
+        self.draw_button(
+            display,
+            theme,
+            Button::Confirm,
+            dial::CENTER + BUTTON_SPACING / 2,
+        )?;
+
+        Ok(())
+    }
+}
+
+// Toasts and dialogs drawn on top of whichever view is shown. Toasts are shown one at a time in
+// the order they arrived. A dialog takes all input until it is answered.
+#[derive(Debug, Default)]
+pub(super) struct Overlay {
+    toasts: Vec<Toast>,
+    // Time the first toast has been shown for.
+    shown: Duration,
+    dialog: Option<Dialog>,
+    dirty: bool,
+}
+
+impl Overlay {
+    pub(super) fn show_toast(&mut self, toast: Toast) {
+        debug!("Queueing {:?} toast: {}", toast.level, toast.message);
+
+        if self.toasts.len() > MAX_QUEUED_TOASTS {
+            // The first toast is on screen, so drop the oldest one still waiting.
+            self.toasts.remove(1);
+        }
+
+        self.dirty |= self.toasts.is_empty();
+        self.toasts.push(toast);
+    }
+
+    pub(super) fn show_dialog(&mut self, dialog: Dialog) {
+        debug!("Showing dialog: {}", dialog.message);
+
+        if self.dialog.is_some() {
+            debug!("Replacing unanswered dialog");
+        }
+
+        self.dialog = Some(dialog);
+        self.dirty = true;
+    }
+
+    pub(super) fn has_dialog(&self) -> bool {
+        self.dialog.is_some()
+    }
+
+    // Whether a toast or dialog is on screen.
+    pub(super) fn is_shown(&self) -> bool {
+        !self.toasts.is_empty() || self.dialog.is_some()
+    }
+
+    // Takes the event when a dialog is shown and returns the action to carry out. Without a
+    // dialog, events are left for the focused view.
+    pub(super) fn handle_event(&mut self, event: InputEvent) -> Option<ViewAction> {
+        let dialog = self.dialog.as_mut()?;
+
+        match event {
+            InputEvent::Rotate { delta, .. } => {
+                let selected = match delta.signum() {
+                    1 => Button::Confirm,
+                    -1 => Button::Cancel,
+                    _ => dialog.selected,
+                };
+
+                if selected == dialog.selected {
+                    return Some(ViewAction::None);
+                }
+
+                dialog.selected = selected;
+                self.dirty = true;
+
+                Some(ViewAction::None)
+            },
+            InputEvent::Press => {
+                let dialog = self.dialog.take()?;
+                self.dirty = true;
+
+                debug!("Dialog answered with {:?}", dialog.selected);
+
+                match dialog.selected {
+                    Button::Confirm => Some(dialog.on_confirm),
+                    Button::Cancel => Some(ViewAction::None),
+                }
+            },
+            _ => Some(ViewAction::None),
+        }
+    }
+
+    pub(super) fn tick(&mut self, dt: Duration) {
+        let Some(toast) = self.toasts.first() else {
+            return;
+        };
+
+        self.shown += dt;
+        if self.shown >= toast.duration {
+            self.toasts.remove(0);
+            self.shown = Duration::from_ticks(0);
+            self.dirty = true;
+        }
+    }
+
+    pub(super) fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        if let Some(toast) = self.toasts.first() {
+            render_toast(display, theme, toast)?;
+        }
+
+        if let Some(dialog) = &self.dialog {
+            dialog.render(display, theme)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    pub(super) fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+fn render_toast(display: &mut Display, theme: &Theme, toast: &Toast) -> Result<(), DisplayError> {
+    let text = Text {
+        content: toast.message.clone(),
+        x: dial::CENTER,
+        y: TOAST_Y,
+        alignment: Alignment::Center,
+        color: theme.foreground,
+        size: theme.caption_font,
+    }
+    .fit_to_circle(Overflow::Ellipsize, 2 * TOAST_PADDING);
+
+    let metrics = text.measure();
+    let width = metrics.width as u16 + 2 * TOAST_PADDING;
+    let height = metrics.height as u16 + TOAST_PADDING;
+
+    let background = match toast.level {
+        ToastLevel::Info => theme.muted,
+        ToastLevel::Error => theme.warning,
+    };
+
+    display.draw(&RoundedRectangle {
+        x: dial::CENTER - width / 2,
+        y: TOAST_Y - height / 2,
+        width,
+        height,
+        radius: height / 2,
+        style: Style::fill(background),
+    })?;
+
+    display.draw(&text)?;
+
+    Ok(())
+}
@@ -0,0 +1,411 @@
+use alloc::vec::Vec;
+
+use embedded_graphics::prelude::Dimensions;
+use log::debug;
+
+use crate::peripherals::display::{
+    Display,
+    error::DisplayError,
+    graphics::{
+        Color, DrawContext, Ellipse, Fill, FilledArc, FilledCircle, FilledRectangle, Graphic, Icon,
+        Line, Point, Polygon, Polyline, Rectangle, RoundedRectangle, Size, Sprite, Text, Triangle,
+    },
+};
+
+// Above this share of the screen, dirty regions are replaced by a single full-screen redraw.
+const FULL_REDRAW_PERCENT: u32 = 60;
+
+pub type NodeId = usize;
+
+#[derive(Clone, PartialEq)]
+pub enum Shape {
+    Circle(FilledCircle),
+    Rectangle(FilledRectangle),
+    Arc(FilledArc),
+    RoundedRectangle(RoundedRectangle),
+    Line(Line),
+    Polyline(Polyline),
+    Polygon(Polygon),
+    Triangle(Triangle),
+    Ellipse(Ellipse),
+    Text(Text),
+    Icon(Icon),
+    Sprite(Sprite),
+}
+
+struct Node {
+    shape: Shape,
+    visible: bool,
+    opacity: u8,
+    changed: bool,
+    // Area covered the last time the node was drawn.
+    drawn: Option<Rectangle>,
+}
+
+// Retained set of shapes drawn in insertion order. Only the areas of nodes that changed since the
+// previous frame are redrawn.
+pub struct Scene {
+    nodes: Vec<Option<Node>>,
+    background: Color,
+    damage: Vec<Rectangle>,
+    full_redraw: bool,
+}
+
+impl Scene {
+    pub fn new(background: Color) -> Self {
+        Self {
+            nodes: Vec::new(),
+            background,
+            damage: Vec::new(),
+            full_redraw: true,
+        }
+    }
+
+    pub fn add(&mut self, shape: impl Into<Shape>) -> NodeId {
+        let node = Node {
+            shape: shape.into(),
+            visible: true,
+            opacity: u8::MAX,
+            changed: true,
+            drawn: None,
+        };
+
+        match self.nodes.iter().position(Option::is_none) {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            },
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            },
+        }
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<Shape> {
+        let node = self.nodes.get_mut(id)?.take()?;
+        self.damage.extend(node.drawn);
+
+        Some(node.shape)
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&Shape> {
+        self.node(id).map(|node| &node.shape)
+    }
+
+    // Replaces a node's shape, marking it for redraw only if it differs from the current one.
+    pub fn set(&mut self, id: NodeId, shape: impl Into<Shape>) {
+        let shape = shape.into();
+
+        if let Some(node) = self.node_mut(id)
+            && node.shape != shape
+        {
+            node.shape = shape;
+            node.changed = true;
+        }
+    }
+
+    pub fn update<F>(&mut self, id: NodeId, f: F)
+    where
+        F: FnOnce(&mut Shape),
+    {
+        if let Some(shape) = self.get(id) {
+            let mut shape = shape.clone();
+            f(&mut shape);
+            self.set(id, shape);
+        }
+    }
+
+    pub fn set_visible(&mut self, id: NodeId, visible: bool) {
+        if let Some(node) = self.node_mut(id)
+            && node.visible != visible
+        {
+            node.visible = visible;
+            node.changed = true;
+        }
+    }
+
+    pub fn opacity(&self, id: NodeId) -> Option<u8> {
+        self.node(id).map(|node| node.opacity)
+    }
+
+    pub fn set_opacity(&mut self, id: NodeId, opacity: u8) {
+        if let Some(node) = self.node_mut(id)
+            && node.opacity != opacity
+        {
+            node.opacity = opacity;
+            node.changed = true;
+        }
+    }
+
+    pub fn set_background(&mut self, background: Color) {
+        if self.background != background {
+            self.background = background;
+            self.full_redraw = true;
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.full_redraw = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.full_redraw
+            || !self.damage.is_empty()
+            || self.nodes.iter().flatten().any(|node| node.changed)
+    }
+
+    // Redraws the changed areas into the framebuffer and returns them so they can be flushed with
+    // `Display::render_regions`.
+    pub fn render(&mut self, display: &mut Display) -> Result<Vec<Rectangle>, DisplayError> {
+        let regions = self.dirty_regions(display.bounding_box());
+
+        for region in &regions {
+            let mut root = DrawContext::new(display);
+            let mut context = root.clip(*region);
+
+            context.draw(&FilledRectangle {
+                x: region.top_left.x as u16,
+                y: region.top_left.y as u16,
+                width: region.size.width as u16,
+                height: region.size.height as u16,
+                fill: Fill::Solid(self.background),
+            })?;
+
+            for node in self.nodes.iter().flatten().filter(|node| node.visible) {
+                if intersects(&node.shape.bounds(), region) {
+                    context.with_opacity(node.opacity).draw(&node.shape)?;
+                }
+            }
+        }
+
+        self.mark_drawn();
+
+        debug!("Scene redrew {} regions", regions.len());
+        Ok(regions)
+    }
+
+    // Records every node as drawn where it is now, for when the whole scene was drawn along with
+    // other content instead of through `render`.
+    pub fn mark_drawn(&mut self) {
+        for node in self.nodes.iter_mut().flatten() {
+            node.drawn = node.visible.then(|| node.shape.bounds());
+            node.changed = false;
+        }
+
+        self.damage.clear();
+        self.full_redraw = false;
+    }
+
+    fn dirty_regions(&self, screen: Rectangle) -> Vec<Rectangle> {
+        if self.full_redraw {
+            return Vec::from([screen]);
+        }
+
+        let mut regions: Vec<Rectangle> = self.damage.clone();
+
+        for node in self.nodes.iter().flatten().filter(|node| node.changed) {
+            regions.extend(node.drawn);
+
+            if node.visible {
+                regions.push(node.shape.bounds());
+            }
+        }
+
+        let mut regions: Vec<Rectangle> = regions
+            .iter()
+            .map(|region| region.intersection(&screen))
+            .filter(|region| !region.is_zero_sized())
+            .collect();
+
+        merge_overlapping(&mut regions);
+
+        let area: u32 = regions.iter().map(|r| r.size.width * r.size.height).sum();
+        let screen_area = screen.size.width * screen.size.height;
+
+        if area * 100 > screen_area * FULL_REDRAW_PERCENT {
+            return Vec::from([screen]);
+        }
+
+        regions
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id)?.as_ref()
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(id)?.as_mut()
+    }
+}
+
+fn intersects(a: &Rectangle, b: &Rectangle) -> bool {
+    !a.intersection(b).is_zero_sized()
+}
+
+fn union(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let top_left = Point::new(
+        a.top_left.x.min(b.top_left.x),
+        a.top_left.y.min(b.top_left.y),
+    );
+    let bottom_right = Point::new(
+        (a.top_left.x + a.size.width as i32).max(b.top_left.x + b.size.width as i32),
+        (a.top_left.y + a.size.height as i32).max(b.top_left.y + b.size.height as i32),
+    );
+
+    Rectangle::new(
+        top_left,
+        Size::new(
+            (bottom_right.x - top_left.x) as u32,
+            (bottom_right.y - top_left.y) as u32,
+        ),
+    )
+}
+
+// Replaces overlapping regions by their union until none overlap.
+fn merge_overlapping(regions: &mut Vec<Rectangle>) {
+    let mut i = 0;
+
+    while i < regions.len() {
+        let overlapping = (i + 1..regions.len()).find(|&j| intersects(&regions[i], &regions[j]));
+
+        match overlapping {
+            Some(j) => {
+                let other = regions.swap_remove(j);
+                regions[i] = union(&regions[i], &other);
+                i = 0;
+            },
+            None => i += 1,
+        }
+    }
+}
+
+// Drawn as a whole, the scene draws its visible nodes over whatever is below them, without its
+// background.
+impl Graphic for Scene {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        for node in self.nodes.iter().flatten().filter(|node| node.visible) {
+            context.with_opacity(node.opacity).draw(&node.shape)?;
+        }
+
+        Ok(())
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.nodes
+            .iter()
+            .flatten()
+            .filter(|node| node.visible)
+            .map(|node| node.shape.bounds())
+            .reduce(|a, b| union(&a, &b))
+            .unwrap_or_default()
+    }
+}
+
+impl Graphic for Shape {
+    fn draw(&self, context: &mut DrawContext) -> Result<(), DisplayError> {
+        match self {
+            Self::Circle(shape) => shape.draw(context),
+            Self::Rectangle(shape) => shape.draw(context),
+            Self::Arc(shape) => shape.draw(context),
+            Self::RoundedRectangle(shape) => shape.draw(context),
+            Self::Line(shape) => shape.draw(context),
+            Self::Polyline(shape) => shape.draw(context),
+            Self::Polygon(shape) => shape.draw(context),
+            Self::Triangle(shape) => shape.draw(context),
+            Self::Ellipse(shape) => shape.draw(context),
+            Self::Text(shape) => shape.draw(context),
+            Self::Icon(shape) => shape.draw(context),
+            Self::Sprite(shape) => shape.draw(context),
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        match self {
+            Self::Circle(shape) => shape.bounds(),
+            Self::Rectangle(shape) => shape.bounds(),
+            Self::Arc(shape) => shape.bounds(),
+            Self::RoundedRectangle(shape) => shape.bounds(),
+            Self::Line(shape) => shape.bounds(),
+            Self::Polyline(shape) => shape.bounds(),
+            Self::Polygon(shape) => shape.bounds(),
+            Self::Triangle(shape) => shape.bounds(),
+            Self::Ellipse(shape) => shape.bounds(),
+            Self::Text(shape) => shape.bounds(),
+            Self::Icon(shape) => shape.bounds(),
+            Self::Sprite(shape) => shape.bounds(),
+        }
+    }
+}
+
+impl From<FilledCircle> for Shape {
+    fn from(shape: FilledCircle) -> Self {
+        Self::Circle(shape)
+    }
+}
+
+impl From<FilledRectangle> for Shape {
+    fn from(shape: FilledRectangle) -> Self {
+        Self::Rectangle(shape)
+    }
+}
+
+impl From<FilledArc> for Shape {
+    fn from(shape: FilledArc) -> Self {
+        Self::Arc(shape)
+    }
+}
+
+impl From<RoundedRectangle> for Shape {
+    fn from(shape: RoundedRectangle) -> Self {
+        Self::RoundedRectangle(shape)
+    }
+}
+
+impl From<Line> for Shape {
+    fn from(shape: Line) -> Self {
+        Self::Line(shape)
+    }
+}
+
+impl From<Polyline> for Shape {
+    fn from(shape: Polyline) -> Self {
+        Self::Polyline(shape)
+    }
+}
+
+impl From<Polygon> for Shape {
+    fn from(shape: Polygon) -> Self {
+        Self::Polygon(shape)
+    }
+}
+
+impl From<Triangle> for Shape {
+    fn from(shape: Triangle) -> Self {
+        Self::Triangle(shape)
+    }
+}
+
+impl From<Ellipse> for Shape {
+    fn from(shape: Ellipse) -> Self {
+        Self::Ellipse(shape)
+    }
+}
+
+impl From<Text> for Shape {
+    fn from(shape: Text) -> Self {
+        Self::Text(shape)
+    }
+}
+
+impl From<Icon> for Shape {
+    fn from(shape: Icon) -> Self {
+        Self::Icon(shape)
+    }
+}
+
+impl From<Sprite> for Shape {
+    fn from(shape: Sprite) -> Self {
+        Self::Sprite(shape)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::peripherals::display::graphics::{Color, FontSize};
+
+// Colours, fonts and stroke widths shared by every view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+    pub foreground: Color,
+    // Secondary text and inactive elements such as the unfilled part of a ring.
+    pub muted: Color,
+    pub accent: Color,
+    pub warning: Color,
+    pub title_font: FontSize,
+    pub body_font: FontSize,
+    pub caption_font: FontSize,
+    pub stroke_width: u16,
+    // Thickness of rings and arcs drawn around the edge of the panel.
+    pub arc_width: u16,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        background: Color::BLACK,
+        foreground: Color::WHITE,
+        muted: Color::DARK_GRAY,
+        accent: Color::AMBER,
+        warning: Color::RED,
+        title_font: FontSize::Large,
+        body_font: FontSize::Medium,
+        caption_font: FontSize::Small,
+        stroke_width: 2,
+        arc_width: 12,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        background: Color::WHITE,
+        foreground: Color::BLACK,
+        muted: Color::LIGHT_GRAY,
+        accent: Color::ORANGE,
+        warning: Color::RED,
+        title_font: FontSize::Large,
+        body_font: FontSize::Medium,
+        caption_font: FontSize::Small,
+        stroke_width: 2,
+        arc_width: 12,
+    };
+
+    // Pure colours, larger text and thicker strokes for readability.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        background: Color::BLACK,
+        foreground: Color::WHITE,
+        muted: Color::GRAY,
+        accent: Color::YELLOW,
+        warning: Color::MAGENTA,
+        title_font: FontSize::Large,
+        body_font: FontSize::Large,
+        caption_font: FontSize::Medium,
+        stroke_width: 3,
+        arc_width: 16,
+    };
+
+    // Built-in theme with the given name, for selecting a theme from settings.
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "high-contrast" => Some(Self::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
@@ -0,0 +1,353 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use embassy_time::{Duration, Instant};
+use embedded_graphics::prelude::OriginDimensions;
+use libm::{cosf, roundf, sinf};
+use log::{debug, warn};
+
+use super::animation::{Easing, Tween};
+use crate::peripherals::display::{
+    Display,
+    error::DisplayError,
+    graphics::{Color, Point, Rectangle, Size},
+};
+
+// Rows of the screen composed at a time.
+const BAND_HEIGHT: usize = 24;
+
+// Fractional bits of the radial wipe direction vector.
+const WIPE_FRACTION_BITS: u32 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transition {
+    // Hard cut to the incoming view.
+    #[default]
+    Cut,
+    // The incoming view pushes the outgoing one out to the left.
+    SlideLeft,
+    // The incoming view pushes the outgoing one out to the right.
+    SlideRight,
+    Fade,
+    // The incoming view grows from the centre over the outgoing one.
+    Zoom,
+    // The incoming view is revealed clockwise from 12 o'clock.
+    RadialWipe,
+}
+
+// Transition in progress. Every frame the incoming views are rendered normally and the screen is
+// then composed one band of rows at a time: the incoming band is set aside, the outgoing views are
+// drawn again clipped to the band, and the two are combined in place. Only one band of scratch
+// memory is needed.
+pub(super) struct ActiveTransition {
+    transition: Transition,
+    band: Vec<u8>,
+    progress: Tween<f32>,
+}
+
+impl ActiveTransition {
+    // Returns `None`, meaning a hard cut, if the transition is `Cut` or there is not enough memory
+    // for the band buffer.
+    pub(super) fn start(
+        transition: Transition,
+        display: &Display,
+        now: Instant,
+        duration: Duration,
+        easing: Easing,
+    ) -> Option<Self> {
+        if transition == Transition::Cut {
+            return None;
+        }
+
+        let band_size = BAND_HEIGHT * display.size().width as usize * 2;
+
+        let mut band = Vec::new();
+        if band.try_reserve_exact(band_size).is_err() {
+            warn!("Not enough memory for a {:?} transition", transition);
+            return None;
+        }
+        band.resize(band_size, 0);
+
+        debug!("Starting {:?} transition", transition);
+        Some(Self {
+            transition,
+            band,
+            progress: Tween::new(0.0, 1.0, now, duration, easing),
+        })
+    }
+
+    pub(super) fn is_finished(&self, now: Instant) -> bool {
+        self.progress.is_finished(now)
+    }
+
+    // Combines the incoming views, already rendered into the framebuffer, with the outgoing ones,
+    // which `draw_outgoing` draws into the clip area it is given.
+    pub(super) fn compose<F>(
+        &mut self,
+        display: &mut Display,
+        now: Instant,
+        mut draw_outgoing: F,
+    ) -> Result<(), DisplayError>
+    where
+        F: FnMut(&mut Display) -> Result<(), DisplayError>,
+    {
+        let t = self.progress.value(now).clamp(0.0, 1.0);
+        if self.transition == Transition::Cut || t >= 1.0 {
+            return Ok(());
+        }
+
+        let size = display.size();
+        let (width, height) = (size.width as usize, size.height as usize);
+        let row_size = width * 2;
+
+        for rows in bands(height) {
+            let area = rows.start * row_size..rows.end * row_size;
+            let band = &mut self.band[..area.len()];
+            band.copy_from_slice(&display.frame()[area.clone()]);
+
+            display.set_clip(Rectangle::new(
+                Point::new(0, rows.start as i32),
+                Size::new(width as u32, rows.len() as u32),
+            ));
+            let drawn = draw_outgoing(display);
+            display.reset_clip();
+            drawn?;
+
+            let frame = display.frame_mut();
+
+            match self.transition {
+                Transition::Cut => {},
+                Transition::SlideLeft | Transition::SlideRight => {
+                    let shift = roundf(width as f32 * t) as usize * 2;
+                    let left = self.transition == Transition::SlideLeft;
+                    slide(&mut frame[area], band, row_size, shift, left);
+                },
+                Transition::Fade => fade(&mut frame[area], band, roundf(t * u8::MAX as f32) as u8),
+                Transition::Zoom => zoom(frame, band, rows, width, height, t),
+                Transition::RadialWipe => wipe(&mut frame[area], band, rows, width, height, t),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Bands of rows in the order they are composed, from the middle of the screen outwards, so that
+// zooming only ever reads incoming rows from bands that are not composed yet.
+fn bands(height: usize) -> impl Iterator<Item = Range<usize>> {
+    let middle = height / 2;
+    let upper = (0..middle)
+        .rev()
+        .step_by(BAND_HEIGHT)
+        .map(|bottom| bottom.saturating_sub(BAND_HEIGHT - 1)..bottom + 1);
+    let lower = (middle..height)
+        .step_by(BAND_HEIGHT)
+        .map(move |top| top..(top + BAND_HEIGHT).min(height));
+
+    upper.chain(lower)
+}
+
+// The incoming view pushes the outgoing one out by `shift` bytes of each row.
+fn slide(outgoing: &mut [u8], incoming: &[u8], row_size: usize, shift: usize, left: bool) {
+    for (outgoing, incoming) in outgoing
+        .chunks_exact_mut(row_size)
+        .zip(incoming.chunks_exact(row_size))
+    {
+        if left {
+            outgoing.copy_within(shift.., 0);
+            outgoing[row_size - shift..].copy_from_slice(&incoming[..shift]);
+        } else {
+            outgoing.copy_within(..row_size - shift, shift);
+            outgoing[..shift].copy_from_slice(&incoming[row_size - shift..]);
+        }
+    }
+}
+
+fn fade(outgoing: &mut [u8], incoming: &[u8], alpha: u8) {
+    for (outgoing, incoming) in outgoing.chunks_exact_mut(2).zip(incoming.chunks_exact(2)) {
+        let from = Color::from_rgb565(u16::from_be_bytes([outgoing[0], outgoing[1]]));
+        let to = Color::from_rgb565(u16::from_be_bytes([incoming[0], incoming[1]]));
+
+        outgoing.copy_from_slice(&from.lerp(to, alpha).to_rgb565().to_be_bytes());
+    }
+}
+
+// Scales the incoming view by `t` around the centre, showing the outgoing view around it. Every
+// row reads the incoming row further from the centre that lands on it, from `band` if it is one of
+// `rows` and from the frame otherwise, where bands composed later still hold the incoming view.
+fn zoom(frame: &mut [u8], band: &[u8], rows: Range<usize>, width: usize, height: usize, t: f32) {
+    let row_size = width * 2;
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    let scale = t.max(f32::EPSILON);
+
+    for y in rows.clone() {
+        let source_y = cy + (y as f32 + 0.5 - cy) / scale;
+        if source_y < 0.0 || source_y >= height as f32 {
+            continue;
+        }
+        let source_y = source_y as usize;
+
+        let (source, destination): (&[u8], &mut [u8]) = if rows.contains(&source_y) {
+            let offset = (source_y - rows.start) * row_size;
+            (
+                &band[offset..offset + row_size],
+                &mut frame[y * row_size..(y + 1) * row_size],
+            )
+        } else if source_y < y {
+            let (before, after) = frame.split_at_mut(y * row_size);
+            (
+                &before[source_y * row_size..(source_y + 1) * row_size],
+                &mut after[..row_size],
+            )
+        } else {
+            let (before, after) = frame.split_at_mut(source_y * row_size);
+            (
+                &after[..row_size],
+                &mut before[y * row_size..(y + 1) * row_size],
+            )
+        };
+
+        for x in 0..width {
+            let source_x = cx + (x as f32 + 0.5 - cx) / scale;
+
+            if source_x >= 0.0 && source_x < width as f32 {
+                let source_x = source_x as usize * 2;
+                destination[x * 2..x * 2 + 2].copy_from_slice(&source[source_x..source_x + 2]);
+            }
+        }
+    }
+}
+
+// Shows the incoming view inside the sector swept clockwise from 12 o'clock by `t` of a full turn
+// and keeps the outgoing view elsewhere. Pixels are classified with integer cross products against
+// the sweep direction instead of computing their angle.
+fn wipe(
+    outgoing: &mut [u8],
+    incoming: &[u8],
+    rows: Range<usize>,
+    width: usize,
+    height: usize,
+    t: f32,
+) {
+    let sweep = t * 2.0 * core::f32::consts::PI;
+    let one = (1 << WIPE_FRACTION_BITS) as f32;
+    let (ux, uy) = (
+        roundf(sinf(sweep) * one) as i32,
+        roundf(-cosf(sweep) * one) as i32,
+    );
+    let past_half = t > 0.5;
+
+    for (row, y) in rows.enumerate() {
+        // Doubled offsets from the centre of the panel to the centre of each pixel.
+        let dy = 2 * y as i32 + 1 - height as i32;
+
+        for x in 0..width {
+            let dx = 2 * x as i32 + 1 - width as i32;
+
+            let right_half = dx > 0 || (dx == 0 && dy < 0);
+            let before_sweep = dx * uy - dy * ux > 0;
+            let revealed = if past_half {
+                right_half || before_sweep
+            } else {
+                right_half && before_sweep
+            };
+
+            if revealed {
+                let offset = (row * width + x) * 2;
+                outgoing[offset..offset + 2].copy_from_slice(&incoming[offset..offset + 2]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::{Output, spi::SpiInterface};
+
+    const INCOMING: Color = Color::RED;
+    const OUTGOING: Color = Color::BLUE;
+    const DURATION: Duration = Duration::from_millis(100);
+
+    // Composes `transition` at progress `t`, with the incoming view filling the screen with one
+    // colour and the outgoing view with another.
+    fn compose(transition: Transition, t: f32) -> Display {
+        let mut display = Display::new(SpiInterface, Output::default(), Output::default());
+        display.clear(INCOMING);
+
+        let start = Instant::from_millis(0);
+        let now = start + Duration::from_micros((DURATION.as_micros() as f32 * t) as u64);
+        let mut active =
+            ActiveTransition::start(transition, &display, start, DURATION, Easing::Linear).unwrap();
+
+        active
+            .compose(&mut display, now, |display| {
+                display.clear(OUTGOING);
+                Ok(())
+            })
+            .unwrap();
+
+        display
+    }
+
+    fn pixel(display: &Display, x: u16, y: u16) -> Color {
+        Color::from_rgb565(display.get_pixel(x, y))
+    }
+
+    #[test]
+    fn bands_cover_every_row_once() {
+        for height in [1, 23, 24, 25, 240] {
+            let mut rows: Vec<usize> = bands(height).flatten().collect();
+            rows.sort_unstable();
+
+            assert_eq!(rows, (0..height).collect::<Vec<_>>(), "height {}", height);
+        }
+    }
+
+    #[test]
+    fn slide_left_halfway() {
+        let display = compose(Transition::SlideLeft, 0.5);
+
+        for y in [0, 119, 120, 239] {
+            assert_eq!(pixel(&display, 0, y), OUTGOING);
+            assert_eq!(pixel(&display, 119, y), OUTGOING);
+            assert_eq!(pixel(&display, 120, y), INCOMING);
+            assert_eq!(pixel(&display, 239, y), INCOMING);
+        }
+    }
+
+    #[test]
+    fn zoom_grows_from_the_centre() {
+        let display = compose(Transition::Zoom, 0.5);
+
+        assert_eq!(pixel(&display, 120, 120), INCOMING);
+        assert_eq!(pixel(&display, 61, 61), INCOMING);
+        assert_eq!(pixel(&display, 59, 59), OUTGOING);
+        assert_eq!(pixel(&display, 0, 239), OUTGOING);
+    }
+
+    #[test]
+    fn wipe_reveals_clockwise() {
+        let display = compose(Transition::RadialWipe, 0.25);
+
+        assert_eq!(pixel(&display, 200, 60), INCOMING);
+        assert_eq!(pixel(&display, 200, 180), OUTGOING);
+        assert_eq!(pixel(&display, 40, 60), OUTGOING);
+    }
+
+    #[test]
+    fn fade_mixes_both_views() {
+        let display = compose(Transition::Fade, 0.5);
+        let mixed = pixel(&display, 10, 200);
+
+        assert!(mixed.0 > 100 && mixed.2 > 100, "{:?}", mixed);
+    }
+
+    #[test]
+    fn finished_transition_leaves_incoming_view() {
+        let display = compose(Transition::SlideRight, 1.0);
+
+        assert_eq!(pixel(&display, 0, 0), INCOMING);
+        assert_eq!(pixel(&display, 239, 239), INCOMING);
+    }
+}
@@ -0,0 +1,305 @@
+use alloc::{format, rc::Rc, string::String, vec::Vec};
+use core::mem;
+
+use embassy_time::{Duration, Instant};
+use libm::{cosf, roundf, sinf};
+
+use super::{Display, Theme, View, dial};
+use crate::{
+    clock::{TimeSource, WallTime},
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, Color, FilledCircle, Line, Point, Stroke, Text},
+    },
+    ui::{
+        InputEvent, NodeId, Scene, Shape, ViewAction,
+        animation::{Animator, Easing, Property},
+    },
+};
+
+// Largest distance the face is moved from the centre to spread wear on the panel. It moves to
+// the next of nine positions every minute.
+const PIXEL_SHIFT: i32 = 3;
+
+const MARK_OUTER: f32 = 108.0;
+const MARK_INNER: f32 = 98.0;
+const HOUR_HAND: f32 = 55.0;
+const MINUTE_HAND: f32 = 80.0;
+const SECOND_HAND: f32 = 90.0;
+
+// The face fades in when shown and when switching faces.
+const FADE_DURATION: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClockFace {
+    #[default]
+    Analog,
+    Digital,
+}
+
+// Clock used as a screensaver. Pressing switches between the analog and digital faces, while
+// turning the knob closes the screensaver.
+pub struct ClockView {
+    source: Option<Rc<dyn TimeSource>>,
+    face: ClockFace,
+    time: Option<WallTime>,
+    // Face as last drawn. Its nodes are replaced when the number of shapes changes, as when
+    // switching faces, and updated in place otherwise, so only the hands that moved are redrawn.
+    scene: Scene,
+    nodes: Vec<NodeId>,
+    fade_in: bool,
+    dirty: bool,
+}
+
+impl ClockView {
+    pub fn with_source(mut self, source: Rc<dyn TimeSource>) -> Self {
+        self.time = source.now();
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_face(mut self, face: ClockFace) -> Self {
+        self.face = face;
+        self
+    }
+
+    fn refresh(&mut self) {
+        let time = self.source.as_ref().and_then(|source| source.now());
+
+        if time != self.time {
+            self.time = time;
+            self.dirty = true;
+        }
+    }
+
+    fn shift(&self) -> Point {
+        let minute = self.time.map_or(0, |time| time.seconds_of_day() / 60);
+        let step = (minute % 9) as i32;
+
+        Point::new((step % 3 - 1) * PIXEL_SHIFT, (step / 3 - 1) * PIXEL_SHIFT)
+    }
+
+    // Shapes of the face showing the current time, moved by the pixel shift.
+    fn shapes(&self, theme: &Theme) -> Vec<Shape> {
+        let shift = self.shift();
+        let center = Point::new(dial::CENTER as i32 + shift.x, dial::CENTER as i32 + shift.y);
+
+        let Some(time) = self.time else {
+            return Vec::from([Shape::Text(Text {
+                content: String::from("--:--"),
+                x: center.x as u16,
+                y: center.y as u16,
+                alignment: Alignment::Center,
+                color: theme.muted,
+                size: theme.title_font,
+            })]);
+        };
+
+        match self.face {
+            ClockFace::Analog => analog(center, theme, time),
+            ClockFace::Digital => digital(center, theme, time),
+        }
+    }
+}
+
+fn analog(center: Point, theme: &Theme, time: WallTime) -> Vec<Shape> {
+    let mut shapes = Vec::with_capacity(16);
+
+    for hour in 0..12 {
+        let width = if hour % 3 == 0 {
+            theme.stroke_width * 2
+        } else {
+            theme.stroke_width
+        };
+
+        shapes.push(Shape::Line(hand(
+            center,
+            hour as f32 / 12.0,
+            MARK_INNER,
+            MARK_OUTER,
+            Stroke {
+                color: theme.muted,
+                width,
+            },
+        )));
+    }
+
+    let seconds = time.seconds() as f32;
+    let minutes = time.minutes() as f32 + seconds / 60.0;
+    let hours = (time.hours() % 12) as f32 + minutes / 60.0;
+
+    let stroke = |width| Stroke {
+        color: theme.foreground,
+        width,
+    };
+
+    shapes.push(Shape::Line(hand(
+        center,
+        hours / 12.0,
+        0.0,
+        HOUR_HAND,
+        stroke(theme.stroke_width * 3),
+    )));
+    shapes.push(Shape::Line(hand(
+        center,
+        minutes / 60.0,
+        0.0,
+        MINUTE_HAND,
+        stroke(theme.stroke_width * 2),
+    )));
+    shapes.push(Shape::Line(hand(
+        center,
+        seconds / 60.0,
+        0.0,
+        SECOND_HAND,
+        Stroke {
+            color: theme.accent,
+            width: theme.stroke_width,
+        },
+    )));
+
+    shapes.push(Shape::Circle(FilledCircle {
+        x: center.x as u16,
+        y: center.y as u16,
+        diameter: theme.stroke_width * 4,
+        fill: theme.accent.into(),
+    }));
+
+    shapes
+}
+
+fn digital(center: Point, theme: &Theme, time: WallTime) -> Vec<Shape> {
+    Vec::from([
+        Shape::Text(Text {
+            content: format!("{:02}:{:02}", time.hours(), time.minutes()),
+            x: center.x as u16,
+            y: center.y as u16,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.title_font,
+        }),
+        Shape::Text(Text {
+            content: format!("{:02}", time.seconds()),
+            x: center.x as u16,
+            y: center.y as u16 + 30,
+            alignment: Alignment::Center,
+            color: theme.muted,
+            size: theme.caption_font,
+        }),
+    ])
+}
+
+// Line along the direction of `turn`, a fraction of a full turn clockwise from 12 o'clock, from
+// `inner` to `outer` pixels away from `center`.
+fn hand(center: Point, turn: f32, inner: f32, outer: f32, stroke: Stroke) -> Line {
+    let angle = (turn * 360.0 - 90.0).to_radians();
+    let (cos, sin) = (cosf(angle), sinf(angle));
+    let point = |radius: f32| {
+        (
+            roundf(center.x as f32 + radius * cos) as u16,
+            roundf(center.y as f32 + radius * sin) as u16,
+        )
+    };
+
+    let (x1, y1) = point(inner);
+    let (x2, y2) = point(outer);
+
+    Line {
+        x1,
+        y1,
+        x2,
+        y2,
+        stroke,
+    }
+}
+
+impl View for ClockView {
+    fn new(_name: &str) -> Self {
+        Self {
+            source: None,
+            face: ClockFace::default(),
+            time: None,
+            scene: Scene::new(Color::BLACK),
+            nodes: Vec::new(),
+            fade_in: true,
+            dirty: true,
+        }
+    }
+
+    // Draws the face as of the last call to `scene`.
+    fn render(&self, display: &mut Display, _theme: &Theme) -> Result<(), DisplayError> {
+        display.draw(&self.scene)?;
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Press => {
+                self.face = match self.face {
+                    ClockFace::Analog => ClockFace::Digital,
+                    ClockFace::Digital => ClockFace::Analog,
+                };
+                self.fade_in = true;
+                ViewAction::Redraw
+            },
+            _ => ViewAction::None,
+        }
+    }
+
+    fn on_enter(&mut self) {
+        self.refresh();
+        self.fade_in = true;
+        self.dirty = true;
+    }
+
+    fn tick(&mut self, _dt: Duration) {
+        self.refresh();
+    }
+
+    fn scene(&mut self, theme: &Theme) -> Option<&mut Scene> {
+        let shapes = self.shapes(theme);
+
+        if shapes.len() != self.nodes.len() {
+            for id in self.nodes.drain(..) {
+                self.scene.remove(id);
+            }
+            self.nodes = shapes
+                .into_iter()
+                .map(|shape| self.scene.add(shape))
+                .collect();
+        } else {
+            for (&id, shape) in self.nodes.iter().zip(shapes) {
+                self.scene.set(id, shape);
+            }
+        }
+
+        self.scene.set_background(theme.background);
+        Some(&mut self.scene)
+    }
+
+    fn animate(&mut self, animator: &mut Animator, now: Instant) {
+        if !mem::take(&mut self.fade_in) {
+            return;
+        }
+
+        for &id in &self.nodes {
+            self.scene.set_opacity(id, 0);
+            animator.animate(
+                &self.scene,
+                id,
+                Property::Opacity(u8::MAX),
+                now,
+                FADE_DURATION,
+                Easing::CubicOut,
+            );
+        }
+    }
+
+    fn needs_redraw(&self) -> bool {
+        self.dirty || self.scene.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
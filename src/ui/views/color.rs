@@ -0,0 +1,272 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use libm::{cosf, roundf, sinf};
+
+use super::{Binding, Display, Theme, View, dial};
+use crate::{
+    entity::Entity,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, Color, FilledArc, FilledCircle, Hsv, Overflow, Text},
+    },
+    ui::{CommandAction, InputEvent, ViewAction},
+};
+
+// The hue ring is drawn as this many arcs, each filled with the hue at its middle.
+const HUE_SEGMENTS: u16 = 36;
+const HUE_STEP: i32 = 5;
+const RING_DIAMETER: u16 = 224;
+const RING_THICKNESS: u16 = 14;
+
+// Saturation is picked on an inner dial running like the others, from 7:30 to 4:30.
+const SATURATION_START: f32 = 135.0;
+const SATURATION_SWEEP: f32 = 270.0;
+const SATURATION_DIAMETER: u16 = 180;
+const SATURATION_STEP: i32 = 5;
+const MAX_SATURATION: u8 = 100;
+
+const PREVIEW_DIAMETER: u16 = 90;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Channel {
+    #[default]
+    Hue,
+    Saturation,
+}
+
+// Colour picker for RGB lights. Turning the knob moves around the hue ring and pressing switches
+// to the saturation dial and back. The centre shows the chosen colour at full brightness.
+pub struct ColorView {
+    name: String,
+    binding: Binding,
+    // Degrees, 0 to 359.
+    hue: u16,
+    // Percent.
+    saturation: u8,
+    channel: Channel,
+}
+
+impl ColorView {
+    // Updates the colour reported by the light itself, ignoring its brightness. Greys have no hue,
+    // so they keep the one chosen before.
+    pub fn set_color(&mut self, color: Color) {
+        let hsv = color.to_hsv();
+        let saturation = ((hsv.saturation as u16 * MAX_SATURATION as u16 + 127) / 255) as u8;
+        let hue = if hsv.saturation == 0 {
+            self.hue
+        } else {
+            hsv.hue
+        };
+
+        if self.hue != hue || self.saturation != saturation {
+            self.hue = hue;
+            self.saturation = saturation;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        Color::from_hsv(Hsv {
+            hue: self.hue,
+            saturation: ((self.saturation as u16 * 255 + MAX_SATURATION as u16 / 2)
+                / MAX_SATURATION as u16) as u8,
+            value: 255,
+        })
+    }
+
+    fn rotate(&mut self, delta: i32, velocity: f32) -> ViewAction {
+        let steps = dial::steps(delta, velocity);
+
+        match self.channel {
+            Channel::Hue => {
+                self.hue = (self.hue as i32 + steps * HUE_STEP).rem_euclid(360) as u16;
+            },
+            Channel::Saturation => {
+                let saturation = (self.saturation as i32 + steps * SATURATION_STEP)
+                    .clamp(0, MAX_SATURATION as i32) as u8;
+
+                if saturation == self.saturation {
+                    return ViewAction::None;
+                }
+
+                self.saturation = saturation;
+            },
+        }
+
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::SetColor(self.color()))
+    }
+
+    fn switch_channel(&mut self) -> ViewAction {
+        self.channel = match self.channel {
+            Channel::Hue => Channel::Saturation,
+            Channel::Saturation => Channel::Hue,
+        };
+        self.binding.dirty = true;
+
+        ViewAction::Redraw
+    }
+
+    fn draw_hue_ring(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        let sweep = 360.0 / HUE_SEGMENTS as f32;
+
+        for segment in 0..HUE_SEGMENTS {
+            let hue = segment * 360 / HUE_SEGMENTS + 180 / HUE_SEGMENTS;
+
+            display.draw(&FilledArc {
+                x: dial::CENTER,
+                y: dial::CENTER,
+                diameter: RING_DIAMETER,
+                thickness: RING_THICKNESS,
+                start_angle: hue_angle(segment * 360 / HUE_SEGMENTS),
+                // Overlap the next segment slightly so no gaps show between them.
+                sweep_angle: sweep + 0.5,
+                fill: Color::from_hsv(Hsv {
+                    hue,
+                    saturation: 255,
+                    value: 255,
+                })
+                .into(),
+            })?;
+        }
+
+        // Handle on the ring at the chosen hue, larger while rotation changes it.
+        let radius = (RING_DIAMETER - RING_THICKNESS) as f32 / 2.0;
+        let angle = hue_angle(self.hue).to_radians();
+        let (x, y) = (
+            roundf(dial::CENTER as f32 + radius * cosf(angle)) as u16,
+            roundf(dial::CENTER as f32 + radius * sinf(angle)) as u16,
+        );
+        let diameter = match self.channel {
+            Channel::Hue => RING_THICKNESS + 8,
+            Channel::Saturation => RING_THICKNESS,
+        };
+
+        display.draw(&FilledCircle {
+            x,
+            y,
+            diameter,
+            fill: theme.foreground.into(),
+        })?;
+
+        display.draw(&FilledCircle {
+            x,
+            y,
+            diameter: diameter - 2 * theme.stroke_width,
+            fill: Color::from_hsv(Hsv {
+                hue: self.hue,
+                saturation: 255,
+                value: 255,
+            })
+            .into(),
+        })?;
+
+        Ok(())
+    }
+
+    fn draw_saturation(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        let arc = |sweep_angle: f32, color: Color| FilledArc {
+            x: dial::CENTER,
+            y: dial::CENTER,
+            diameter: SATURATION_DIAMETER,
+            thickness: theme.arc_width,
+            start_angle: SATURATION_START,
+            sweep_angle,
+            fill: color.into(),
+        };
+
+        let fraction = self.saturation as f32 / MAX_SATURATION as f32;
+        let color = match self.channel {
+            Channel::Hue => theme.muted,
+            Channel::Saturation => self.color(),
+        };
+
+        display.draw(&arc(SATURATION_SWEEP, theme.muted))?;
+        display.draw(&arc(SATURATION_SWEEP * fraction, color))?;
+
+        Ok(())
+    }
+}
+
+// Position of a hue on the ring, with red at 12 o'clock and hues increasing clockwise.
+fn hue_angle(hue: u16) -> f32 {
+    hue as f32 - 90.0
+}
+
+impl View for ColorView {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            binding: Binding::new(name),
+            hue: 0,
+            saturation: MAX_SATURATION,
+            channel: Channel::default(),
+        }
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        self.draw_hue_ring(display, theme)?;
+        self.draw_saturation(display, theme)?;
+
+        display.draw(&FilledCircle {
+            x: dial::CENTER,
+            y: dial::CENTER,
+            diameter: PREVIEW_DIAMETER,
+            fill: self.color().into(),
+        })?;
+
+        let name = Text {
+            content: self.name.clone(),
+            x: dial::CENTER,
+            y: 58,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.caption_font,
+        }
+        .fit_to_circle(Overflow::Ellipsize, 40);
+
+        display.draw(&name)?;
+
+        let label = match self.channel {
+            Channel::Hue => format!("Hue {}", self.hue),
+            Channel::Saturation => format!("Saturation {}%", self.saturation),
+        };
+
+        display.draw(&Text {
+            content: label,
+            x: dial::CENTER,
+            y: 185,
+            alignment: Alignment::Center,
+            color: theme.muted,
+            size: theme.caption_font,
+        })?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Rotate { delta, velocity } => self.rotate(delta, velocity),
+            InputEvent::Press => self.switch_channel(),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn binding(&self) -> Option<&Binding> {
+        Some(&self.binding)
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        Some(&mut self.binding)
+    }
+
+    fn on_entity_changed(&mut self, entity: &Entity) {
+        if let Some(color) = entity.state.color {
+            self.set_color(color);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    peripherals::display::graphics::{Color, Fill, FilledArc},
+    ui::Theme,
+};
+
+// Centre of the round panel.
+pub const CENTER: u16 = 120;
+
+// Dials run clockwise from 7:30 to 4:30, leaving the bottom of the panel free.
+const START_ANGLE: f32 = 135.0;
+const SWEEP_ANGLE: f32 = 270.0;
+const DIAMETER: u16 = 224;
+
+// Above this speed, in detents per second, each detent moves a dial `FAST_STEP` times further.
+const FAST_VELOCITY: f32 = 10.0;
+const FAST_STEP: i32 = 5;
+
+// Unfilled background of a dial.
+pub fn track(theme: &Theme) -> FilledArc {
+    arc(theme, START_ANGLE, SWEEP_ANGLE, theme.muted)
+}
+
+// Part of a dial filled up to `fraction`, from 0 to 1.
+pub fn value(theme: &Theme, fraction: f32, color: Color) -> FilledArc {
+    arc(
+        theme,
+        START_ANGLE,
+        SWEEP_ANGLE * fraction.clamp(0.0, 1.0),
+        color,
+    )
+}
+
+// Short mark on a dial at `fraction`, for setpoints and limits.
+pub fn marker(theme: &Theme, fraction: f32, color: Color) -> FilledArc {
+    const WIDTH: f32 = 3.0;
+
+    let angle = START_ANGLE + SWEEP_ANGLE * fraction.clamp(0.0, 1.0);
+    arc(theme, angle - WIDTH / 2.0, WIDTH, color)
+}
+
+fn arc(theme: &Theme, start_angle: f32, sweep_angle: f32, color: Color) -> FilledArc {
+    FilledArc {
+        x: CENTER,
+        y: CENTER,
+        diameter: DIAMETER,
+        thickness: theme.arc_width,
+        start_angle,
+        sweep_angle,
+        fill: Fill::Solid(color),
+    }
+}
+
+// Number of steps a rotation moves a value by, accelerated when the knob is turned quickly.
+pub fn steps(delta: i32, velocity: f32) -> i32 {
+    if velocity.abs() > FAST_VELOCITY {
+        delta.saturating_mul(FAST_STEP)
+    } else {
+        delta
+    }
+}
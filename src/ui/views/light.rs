@@ -1,28 +1,184 @@
-use alloc::string::{String, ToString};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 
-use super::{Display, View};
-use crate::peripherals::display::graphics::{Alignment, Color, Text};
+use super::{Binding, Display, Theme, View, dial};
+use crate::{
+    entity::Entity,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, Color, Overflow, Text},
+    },
+    ui::{CommandAction, InputEvent, ViewAction},
+};
 
+const MAX_BRIGHTNESS: u8 = 100;
+
+// Dimmable light. Pressing toggles it and turning the knob changes the brightness, in percent.
 pub struct LightView {
     name: String,
+    binding: Binding,
+    on: bool,
+    brightness: u8,
+    // Colour temperature in Kelvin, for lights that support it.
+    color_temperature: Option<u16>,
+}
+
+impl LightView {
+    pub fn with_color_temperature(mut self, kelvin: u16) -> Self {
+        self.color_temperature = Some(kelvin);
+        self
+    }
+
+    // Updates the state reported by the light itself.
+    pub fn set_state(&mut self, on: bool, brightness: u8) {
+        let brightness = brightness.min(MAX_BRIGHTNESS);
+
+        if self.on != on || self.brightness != brightness {
+            self.on = on;
+            self.brightness = brightness;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn set_color_temperature(&mut self, kelvin: Option<u16>) {
+        if self.color_temperature != kelvin {
+            self.color_temperature = kelvin;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    fn toggle(&mut self) -> ViewAction {
+        self.on = !self.on;
+        if self.on && self.brightness == 0 {
+            self.brightness = MAX_BRIGHTNESS;
+        }
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::Toggle)
+    }
+
+    fn rotate(&mut self, delta: i32, velocity: f32) -> ViewAction {
+        // A light that is off dims up from nothing rather than from the brightness it had.
+        let current = if self.on { self.brightness as i32 } else { 0 };
+        let brightness = current
+            .saturating_add(dial::steps(delta, velocity))
+            .clamp(0, MAX_BRIGHTNESS as i32) as u8;
+
+        // Turning an off light down leaves it off, at the brightness it comes back on at.
+        if brightness == 0 && !self.on || brightness == self.brightness && self.on {
+            return ViewAction::None;
+        }
+
+        self.brightness = brightness;
+        self.on = brightness > 0;
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::Set(brightness as i32))
+    }
+
+    fn arc_color(&self, theme: &Theme) -> Color {
+        match self.color_temperature {
+            Some(kelvin) => Color::from_kelvin(kelvin),
+            None => theme.accent,
+        }
+    }
 }
 
 impl View for LightView {
     fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
+            binding: Binding::new(name),
+            on: false,
+            brightness: MAX_BRIGHTNESS,
+            color_temperature: None,
         }
     }
 
-    fn render(&self, display: &mut Display) {
-        let text = Text {
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        display.draw(&dial::track(theme))?;
+
+        if self.on {
+            let fraction = self.brightness as f32 / MAX_BRIGHTNESS as f32;
+            display.draw(&dial::value(theme, fraction, self.arc_color(theme)))?;
+        }
+
+        let (state, state_color) = if self.on {
+            (format!("{}%", self.brightness), theme.foreground)
+        } else {
+            (String::from("Off"), theme.muted)
+        };
+
+        display.draw(&Text {
+            content: state,
+            x: dial::CENTER,
+            y: 105,
+            alignment: Alignment::Center,
+            color: state_color,
+            size: theme.title_font,
+        })?;
+
+        let name = Text {
             content: self.name.clone(),
-            x: 120,
-            y: 120,
+            x: dial::CENTER,
+            y: 140,
             alignment: Alignment::Center,
-            color: Color::WHITE,
-        };
+            color: theme.foreground,
+            size: theme.body_font,
+        }
+        .fit_to_circle(Overflow::Shrink, 24);
+
+        display.draw(&name)?;
 
-        display.draw(&text);
+        if let Some(kelvin) = self.color_temperature {
+            display.draw(&Text {
+                content: format!("{}K", kelvin),
+                x: dial::CENTER,
+                y: 165,
+                alignment: Alignment::Center,
+                color: theme.muted,
+                size: theme.caption_font,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Press => self.toggle(),
+            InputEvent::Rotate { delta, velocity } => self.rotate(delta, velocity),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn binding(&self) -> Option<&Binding> {
+        Some(&self.binding)
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        Some(&mut self.binding)
+    }
+
+    fn on_entity_changed(&mut self, entity: &Entity) {
+        let state = &entity.state;
+
+        self.set_state(
+            state.on.unwrap_or(self.on),
+            state.brightness.unwrap_or(self.brightness),
+        );
+        if state.color_temperature.is_some() {
+            self.set_color_temperature(state.color_temperature);
+        }
     }
 }
@@ -0,0 +1,290 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::cell::Cell;
+
+use embassy_time::Duration;
+
+use super::{Binding, Display, Theme, View, dial};
+use crate::{
+    entity::Entity,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{
+            Alignment, Color, DrawContext, FilledRectangle, FontSize, Point, Rectangle, Size,
+            Style, Text, Triangle,
+        },
+    },
+    ui::{CommandAction, InputEvent, ViewAction},
+};
+
+const MAX_VOLUME: u8 = 100;
+
+// Distance kept between text and the edge of the panel.
+const TEXT_PADDING: u16 = 16;
+// Scrolling speed of titles that do not fit, in pixels per second.
+const MARQUEE_SPEED: f32 = 30.0;
+// Separator between the end of a scrolling title and its next repetition.
+const MARQUEE_GAP: &str = "   ";
+
+const ICON_Y: u16 = 100;
+const ICON_SIZE: u16 = 28;
+
+// Media player. Turning the knob changes the volume, in percent, and pressing toggles playback.
+pub struct MediaView {
+    name: String,
+    binding: Binding,
+    playing: bool,
+    volume: u8,
+    title: String,
+    artist: String,
+    // Pixels the marquee has scrolled since the track changed, wrapped by `cycle`.
+    scroll: f32,
+    // Set when the last render found text too wide for the panel, so ticks should scroll it, to
+    // the distance after which every scrolling line is back where it started.
+    cycle: Cell<Option<u32>>,
+}
+
+impl MediaView {
+    // Updates the state reported by the player itself.
+    pub fn set_state(&mut self, playing: bool, volume: u8) {
+        let volume = volume.min(MAX_VOLUME);
+
+        if self.playing != playing || self.volume != volume {
+            self.playing = playing;
+            self.volume = volume;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn set_track(&mut self, title: &str, artist: &str) {
+        if self.title != title || self.artist != artist {
+            self.title = title.to_string();
+            self.artist = artist.to_string();
+            self.scroll = 0.0;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn volume(&self) -> u8 {
+        self.volume
+    }
+
+    fn toggle(&mut self) -> ViewAction {
+        self.playing = !self.playing;
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::Select(if self.playing {
+            "play"
+        } else {
+            "pause"
+        }))
+    }
+
+    fn rotate(&mut self, delta: i32, velocity: f32) -> ViewAction {
+        let volume =
+            (self.volume as i32 + dial::steps(delta, velocity)).clamp(0, MAX_VOLUME as i32) as u8;
+
+        if volume == self.volume {
+            return ViewAction::None;
+        }
+
+        self.volume = volume;
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::Set(volume as i32))
+    }
+
+    fn draw_icon(&self, display: &mut Display, color: Color) -> Result<(), DisplayError> {
+        let (x, y, half) = (dial::CENTER, ICON_Y, ICON_SIZE / 2);
+
+        if self.playing {
+            display.draw(&Triangle {
+                x1: x - half + 3,
+                y1: y - half,
+                x2: x - half + 3,
+                y2: y + half,
+                x3: x + half + 3,
+                y3: y,
+                style: Style::fill(color),
+            })?;
+        } else {
+            let bar = ICON_SIZE / 3;
+
+            for left in [x - half, x + half - bar] {
+                display.draw(&FilledRectangle {
+                    x: left,
+                    y: y - half,
+                    width: bar,
+                    height: ICON_SIZE,
+                    fill: color.into(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Draws a centred line of text, scrolling it horizontally when it is too wide for the panel.
+    // Returns the distance it scrolls before repeating, if it scrolls.
+    fn draw_line(
+        &self,
+        display: &mut Display,
+        content: &str,
+        y: u16,
+        color: Color,
+        size: FontSize,
+    ) -> Result<Option<u32>, DisplayError> {
+        let text = Text {
+            content: content.to_string(),
+            x: dial::CENTER,
+            y,
+            alignment: Alignment::Center,
+            color,
+            size,
+        };
+
+        let available = text.available_width(TEXT_PADDING);
+        if size.width_of(content.chars().count()) <= available {
+            display.draw(&text)?;
+            return Ok(None);
+        }
+
+        let advance = size.width_of(2) - size.width_of(1);
+        let cycle = advance * (content.chars().count() + MARQUEE_GAP.len()) as u32;
+        let offset = self.scroll as u32 % cycle;
+
+        let left = dial::CENTER as i32 - available as i32 / 2;
+        let height = size.char_height();
+        let area = Rectangle::new(
+            Point::new(left, y as i32 - height as i32 / 2 - 1),
+            Size::new(available, height + 2),
+        );
+
+        let mut root = DrawContext::new(display);
+        let mut clipped = root.clip(area);
+        let mut context = clipped.translate(Point::new(-(offset as i32), 0));
+
+        context.draw(&Text {
+            content: format!("{}{}{}", content, MARQUEE_GAP, content),
+            x: left as u16,
+            alignment: Alignment::Left,
+            ..text
+        })?;
+
+        Ok(Some(cycle))
+    }
+}
+
+impl View for MediaView {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            binding: Binding::new(name),
+            playing: false,
+            volume: 50,
+            title: String::new(),
+            artist: String::new(),
+            scroll: 0.0,
+            cycle: Cell::new(None),
+        }
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        let fraction = self.volume as f32 / MAX_VOLUME as f32;
+
+        display.draw(&dial::track(theme))?;
+        display.draw(&dial::value(theme, fraction, theme.accent))?;
+
+        display.draw(&Text {
+            content: format!("{}%", self.volume),
+            x: dial::CENTER,
+            y: 60,
+            alignment: Alignment::Center,
+            color: theme.muted,
+            size: theme.caption_font,
+        })?;
+
+        self.draw_icon(display, theme.foreground)?;
+
+        let title = if self.title.is_empty() {
+            &self.name
+        } else {
+            &self.title
+        };
+
+        let mut cycle = self.draw_line(display, title, 145, theme.foreground, theme.body_font)?;
+
+        if !self.artist.is_empty()
+            && let Some(artist) =
+                self.draw_line(display, &self.artist, 170, theme.muted, theme.caption_font)?
+        {
+            cycle = Some(cycle.map_or(artist, |title| lcm(title, artist)));
+        }
+
+        self.cycle.set(cycle);
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Press => self.toggle(),
+            InputEvent::Rotate { delta, velocity } => self.rotate(delta, velocity),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn on_enter(&mut self) {
+        self.scroll = 0.0;
+    }
+
+    fn tick(&mut self, dt: Duration) {
+        if let Some(cycle) = self.cycle.get() {
+            let distance = MARQUEE_SPEED * dt.as_micros() as f32 / 1_000_000.0;
+            self.scroll = (self.scroll + distance) % cycle as f32;
+            self.binding.dirty = true;
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.cycle.get().is_some()
+    }
+
+    fn binding(&self) -> Option<&Binding> {
+        Some(&self.binding)
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        Some(&mut self.binding)
+    }
+
+    fn on_entity_changed(&mut self, entity: &Entity) {
+        let state = &entity.state;
+
+        self.set_state(
+            state.playing.unwrap_or(self.playing),
+            state.volume.unwrap_or(self.volume),
+        );
+        if state.title.is_some() || state.artist.is_some() {
+            self.set_track(
+                state.title.as_deref().unwrap_or_default(),
+                state.artist.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+}
+
+fn lcm(a: u32, b: u32) -> u32 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
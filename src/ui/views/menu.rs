@@ -0,0 +1,278 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use embassy_time::{Duration, Instant};
+use libm::{cosf, roundf, sinf};
+
+use super::{Display, Theme, View, dial};
+use crate::{
+    assets::Image,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, FilledCircle, Icon, Overflow, Text},
+    },
+    ui::{
+        InputEvent, Navigation, Presentation, ViewAction,
+        animation::{Animated, Easing},
+    },
+};
+
+// Items sit on a ring around the centre, with the focused one at 12 o'clock.
+const RING_RADIUS: f32 = 82.0;
+const ITEM_DIAMETER: u16 = 36;
+const FOCUS_DIAMETER: u16 = 46;
+// Angle between neighbouring items, widened when there are few of them.
+const MIN_SPACING: f32 = 45.0;
+// Items further than this from the focused one, in positions, are not drawn.
+const VISIBLE_RANGE: f32 = 3.5;
+
+const SCROLL_DURATION: Duration = Duration::from_millis(200);
+
+type Factory = Box<dyn Fn() -> Box<dyn View>>;
+
+enum MenuTarget {
+    Open(Factory),
+    Navigate(usize),
+}
+
+pub struct MenuItem {
+    pub label: String,
+    pub icon: Option<&'static Image>,
+    target: MenuTarget,
+}
+
+impl MenuItem {
+    // Item that opens the view built by `factory` as a sub-menu.
+    pub fn open<F>(label: &str, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn View> + 'static,
+    {
+        Self {
+            label: label.to_string(),
+            icon: None,
+            target: MenuTarget::Open(Box::new(factory)),
+        }
+    }
+
+    // Item that jumps to the root view at `index`.
+    pub fn navigate(label: &str, index: usize) -> Self {
+        Self {
+            label: label.to_string(),
+            icon: None,
+            target: MenuTarget::Navigate(index),
+        }
+    }
+
+    pub fn with_icon(mut self, icon: &'static Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+}
+
+// Carousel of items arranged around the panel. Turning the knob rotates the ring by one item per
+// detent and pressing opens the focused one.
+pub struct MenuView {
+    title: String,
+    items: Vec<MenuItem>,
+    // Focused position, not wrapped, so the ring keeps turning in the same direction.
+    focus: i32,
+    position: Animated<f32>,
+    dirty: bool,
+}
+
+impl MenuView {
+    pub fn add(&mut self, item: MenuItem) {
+        self.items.push(item);
+        self.dirty = true;
+    }
+
+    pub fn with_item(mut self, item: MenuItem) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn focused(&self) -> Option<usize> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        Some(self.focus.rem_euclid(self.items.len() as i32) as usize)
+    }
+
+    fn spacing(&self) -> f32 {
+        MIN_SPACING.max(360.0 / self.items.len().max(1) as f32)
+    }
+
+    fn rotate(&mut self, delta: i32) -> ViewAction {
+        if self.items.len() < 2 || delta == 0 {
+            return ViewAction::None;
+        }
+
+        self.focus = self.focus.saturating_add(delta);
+        self.position.animate_to(
+            self.focus as f32,
+            Instant::now(),
+            SCROLL_DURATION,
+            Easing::CubicOut,
+        );
+
+        ViewAction::Redraw
+    }
+
+    fn open(&self) -> ViewAction {
+        let Some(item) = self.focused().and_then(|index| self.items.get(index)) else {
+            return ViewAction::None;
+        };
+
+        match &item.target {
+            MenuTarget::Open(factory) => ViewAction::Open(factory(), Presentation::Screen),
+            MenuTarget::Navigate(index) => ViewAction::Navigate(Navigation::To(*index)),
+        }
+    }
+
+    fn draw_item(
+        &self,
+        display: &mut Display,
+        theme: &Theme,
+        item: &MenuItem,
+        (x, y): (u16, u16),
+        focused: bool,
+    ) -> Result<(), DisplayError> {
+        if focused {
+            display.draw(&FilledCircle {
+                x,
+                y,
+                diameter: FOCUS_DIAMETER,
+                fill: theme.accent.into(),
+            })?;
+        }
+
+        match item.icon {
+            Some(image) => {
+                display.draw(&Icon { image, x, y })?;
+            },
+            None => {
+                let background = if focused {
+                    theme.background
+                } else {
+                    theme.muted
+                };
+
+                display.draw(&FilledCircle {
+                    x,
+                    y,
+                    diameter: ITEM_DIAMETER,
+                    fill: background.into(),
+                })?;
+
+                let initial = item.label.chars().next().map(String::from);
+                display.draw(&Text {
+                    content: initial.unwrap_or_default(),
+                    x,
+                    y,
+                    alignment: Alignment::Center,
+                    color: theme.foreground,
+                    size: theme.body_font,
+                })?;
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl View for MenuView {
+    fn new(name: &str) -> Self {
+        Self {
+            title: name.to_string(),
+            items: Vec::new(),
+            focus: 0,
+            position: Animated::new(0.0),
+            dirty: true,
+        }
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        let len = self.items.len() as i32;
+        let position = self.position.value(Instant::now());
+        let spacing = self.spacing();
+
+        // Every item is drawn once, at the repetition closest to the focused position.
+        for offset in -len / 2..len - len / 2 {
+            let slot = roundf(position) as i32 + offset;
+            let distance = slot as f32 - position;
+            if distance.abs() > VISIBLE_RANGE {
+                continue;
+            }
+
+            let item = &self.items[slot.rem_euclid(len) as usize];
+            let angle = (distance * spacing - 90.0).to_radians();
+            let center = (
+                roundf(dial::CENTER as f32 + RING_RADIUS * cosf(angle)) as u16,
+                roundf(dial::CENTER as f32 + RING_RADIUS * sinf(angle)) as u16,
+            );
+
+            self.draw_item(display, theme, item, center, slot == self.focus)?;
+        }
+
+        let label = match self.focused() {
+            Some(index) => self.items[index].label.clone(),
+            None => String::from("Empty"),
+        };
+
+        let label = Text {
+            content: label,
+            x: dial::CENTER,
+            y: dial::CENTER + 10,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.title_font,
+        }
+        .fit_to_circle(Overflow::Wrap, 48);
+
+        display.draw(&label)?;
+
+        display.draw(
+            &Text {
+                content: self.title.clone(),
+                x: dial::CENTER,
+                y: dial::CENTER - 20,
+                alignment: Alignment::Center,
+                color: theme.muted,
+                size: theme.caption_font,
+            }
+            .fit_to_circle(Overflow::Ellipsize, 16),
+        )?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Rotate { delta, .. } => self.rotate(delta),
+            InputEvent::Press => self.open(),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn tick(&mut self, _dt: Duration) {
+        if self.position.poll(Instant::now()) {
+            self.dirty = true;
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.position.is_animating(Instant::now())
+    }
+
+    fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
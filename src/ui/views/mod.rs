@@ -1,12 +1,134 @@
+mod clock;
+mod color;
+pub(super) mod dial;
 mod light;
+mod media;
+mod menu;
+mod number;
+mod thermostat;
 
-pub use light::LightView;
+use alloc::string::{String, ToString};
+use core::fmt;
 
-use super::Display;
+use embassy_time::{Duration, Instant};
+
+pub use self::{
+    clock::{ClockFace, ClockView},
+    color::ColorView,
+    light::LightView,
+    media::MediaView,
+    menu::{MenuItem, MenuView},
+    number::NumberView,
+    thermostat::{HvacMode, ThermostatView},
+};
+use super::{
+    Command, CommandAction, Display, InputEvent, Scene, Theme, ViewAction, animation::Animator,
+};
+use crate::{entity::Entity, peripherals::display::error::DisplayError};
 
 pub trait View {
     fn new(name: &str) -> Self
     where
         Self: Sized;
-    fn render(&self, display: &mut Display);
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError>;
+
+    // Addresses the view's commands to `entity`. Views without a binding ignore it.
+    fn with_entity(mut self, entity: &str) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(binding) = self.binding_mut() {
+            binding.entity = entity.to_string();
+        }
+        self
+    }
+
+    fn handle_event(&mut self, _event: InputEvent) -> ViewAction {
+        ViewAction::None
+    }
+
+    // Called when the view becomes the current one, to start timers and subscriptions.
+    fn on_enter(&mut self) {}
+
+    // Called when another view replaces this one, to stop them.
+    fn on_exit(&mut self) {}
+
+    // Advances time-based state by `dt`, the time since the previous tick. Only the current view
+    // is ticked.
+    fn tick(&mut self, _dt: Duration) {}
+
+    // Whether the view changed since it was last rendered. Animating views redraw every frame.
+    fn needs_redraw(&self) -> bool {
+        self.binding().is_some_and(|binding| binding.dirty) || self.is_animating()
+    }
+
+    // Called after the view has been rendered, to reset whatever `needs_redraw` reports.
+    fn mark_clean(&mut self) {
+        if let Some(binding) = self.binding_mut() {
+            binding.dirty = false;
+        }
+    }
+
+    // Brings the scene of views that draw through one up to date with their state and `theme`,
+    // and returns it. While such a view stays selected, only the areas of its scene that changed
+    // are redrawn.
+    fn scene(&mut self, _theme: &Theme) -> Option<&mut Scene> {
+        None
+    }
+
+    // Called before each frame while the view has focus, once its scene is up to date, to start
+    // animations on the scene's nodes. They run until they finish or focus moves.
+    fn animate(&mut self, _animator: &mut Animator, _now: Instant) {}
+
+    // Whether the view is animating and should be redrawn at the active frame rate.
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    // Id of the entity the view shows, if it is bound to one.
+    fn entity(&self) -> Option<&str> {
+        self.binding().map(|binding| binding.entity.as_str())
+    }
+
+    // Entity and redraw state of views that control an entity.
+    fn binding(&self) -> Option<&Binding> {
+        None
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        None
+    }
+
+    // Called when the state of the view's entity changed, to show the new state.
+    fn on_entity_changed(&mut self, _entity: &Entity) {}
+}
+
+// Entity a view controls and whether the view changed since it was last rendered. Views that hold
+// one get `with_entity`, `entity`, `needs_redraw` and `mark_clean` from it.
+pub struct Binding {
+    // Id commands are addressed to, the view's name unless set.
+    entity: String,
+    dirty: bool,
+}
+
+impl Binding {
+    pub fn new(name: &str) -> Self {
+        Self {
+            entity: name.to_string(),
+            dirty: true,
+        }
+    }
+
+    pub fn command(&self, action: CommandAction) -> ViewAction {
+        ViewAction::Command(Command {
+            target: self.entity.clone(),
+            action,
+        })
+    }
+}
+
+impl fmt::Debug for dyn View {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "View")
+    }
 }
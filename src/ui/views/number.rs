@@ -0,0 +1,96 @@
+use alloc::string::{String, ToString};
+
+use super::{Binding, Display, Theme, View, dial};
+use crate::{
+    entity::Entity,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, Overflow, Text},
+    },
+    ui::{
+        CommandAction, InputEvent, ViewAction,
+        widgets::{PickerEvent, ValuePicker},
+    },
+};
+
+const DEFAULT_MAX: i32 = 100;
+
+// Entity that is just a number in a range, such as a fan speed or a blind position.
+pub struct NumberView {
+    name: String,
+    binding: Binding,
+    picker: ValuePicker,
+}
+
+impl NumberView {
+    pub fn with_picker(mut self, picker: ValuePicker) -> Self {
+        self.picker = picker;
+        self
+    }
+
+    // Updates the value reported by the entity itself.
+    pub fn set_value(&mut self, value: i32) {
+        self.binding.dirty |= self.picker.set_value(value);
+    }
+
+    pub fn value(&self) -> i32 {
+        self.picker.value()
+    }
+}
+
+impl View for NumberView {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            binding: Binding::new(name),
+            picker: ValuePicker::new(0, DEFAULT_MAX, 1),
+        }
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        self.picker.render(display, theme)?;
+
+        let name = Text {
+            content: self.name.clone(),
+            x: dial::CENTER,
+            y: 140,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.body_font,
+        }
+        .fit_to_circle(Overflow::Shrink, 24);
+
+        display.draw(&name)?;
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match self.picker.handle_event(event) {
+            Some(PickerEvent::ValueChanged(value)) => {
+                self.binding.dirty = true;
+
+                self.binding.command(CommandAction::Set(value))
+            },
+            Some(PickerEvent::ModeChanged(_)) => {
+                self.binding.dirty = true;
+                ViewAction::Redraw
+            },
+            None => ViewAction::None,
+        }
+    }
+
+    fn binding(&self) -> Option<&Binding> {
+        Some(&self.binding)
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        Some(&mut self.binding)
+    }
+
+    fn on_entity_changed(&mut self, entity: &Entity) {
+        if let Some(value) = entity.state.value {
+            self.set_value(value);
+        }
+    }
+}
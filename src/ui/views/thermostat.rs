@@ -0,0 +1,287 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use super::{Binding, Display, Theme, View, dial};
+use crate::{
+    entity::Entity,
+    peripherals::display::{
+        error::DisplayError,
+        graphics::{Alignment, Color, Overflow, Text},
+    },
+    ui::{CommandAction, InputEvent, ViewAction},
+};
+
+// Temperatures are kept in tenths of a degree so setpoint steps are exact.
+const STEP: i32 = 5;
+const DEFAULT_MIN: i32 = 50;
+const DEFAULT_MAX: i32 = 300;
+const DEFAULT_TARGET: i32 = 200;
+
+// Limits are labelled below the ends of the dial, which sit at 7:30 and 4:30.
+const LIMIT_OFFSET: u16 = 50;
+const LIMIT_Y: u16 = 195;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HvacMode {
+    #[default]
+    Off,
+    Heat,
+    Cool,
+    Auto,
+}
+
+impl HvacMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Heat => "heat",
+            Self::Cool => "cool",
+            Self::Auto => "auto",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(Self::Off),
+            "heat" => Some(Self::Heat),
+            "cool" => Some(Self::Cool),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Heat => "Heat",
+            Self::Cool => "Cool",
+            Self::Auto => "Auto",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Heat,
+            Self::Heat => Self::Cool,
+            Self::Cool => Self::Auto,
+            Self::Auto => Self::Off,
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Off => theme.muted,
+            Self::Heat => Color::ORANGE,
+            Self::Cool => Color::CYAN,
+            Self::Auto => theme.accent,
+        }
+    }
+}
+
+// Climate control. Turning the knob moves the setpoint in half-degree steps within the limits and
+// pressing cycles through the HVAC modes. Setpoints are sent in tenths of a degree.
+pub struct ThermostatView {
+    name: String,
+    binding: Binding,
+    mode: HvacMode,
+    current: Option<i32>,
+    target: i32,
+    min: i32,
+    max: i32,
+}
+
+impl ThermostatView {
+    // Limits of the setpoint, in tenths of a degree.
+    pub fn with_limits(mut self, min: i32, max: i32) -> Self {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.target = self.target.clamp(self.min, self.max);
+        self
+    }
+
+    // Updates the state reported by the thermostat itself.
+    pub fn set_state(&mut self, mode: HvacMode, current: Option<i32>, target: i32) {
+        let target = target.clamp(self.min, self.max);
+
+        if self.mode != mode || self.current != current || self.target != target {
+            self.mode = mode;
+            self.current = current;
+            self.target = target;
+            self.binding.dirty = true;
+        }
+    }
+
+    pub fn mode(&self) -> HvacMode {
+        self.mode
+    }
+
+    pub fn target(&self) -> i32 {
+        self.target
+    }
+
+    fn cycle_mode(&mut self) -> ViewAction {
+        self.mode = self.mode.next();
+        self.binding.dirty = true;
+
+        self.binding
+            .command(CommandAction::Select(self.mode.name()))
+    }
+
+    fn rotate(&mut self, delta: i32, velocity: f32) -> ViewAction {
+        let steps = dial::steps(delta, velocity);
+
+        // Steps start from the half degree at or behind the setpoint, so one left off the grid by
+        // a limit such as 18.3 gets back on it instead of moving to 18.8. Worked out in i64 so
+        // neither the rounding nor the step can overflow.
+        let (target, step) = (self.target as i64, STEP as i64);
+        let base = match steps.signum() {
+            1 => target.div_euclid(step) * step,
+            -1 => -(-target).div_euclid(step) * step,
+            _ => target,
+        };
+        let target = (base + steps as i64 * step).clamp(self.min as i64, self.max as i64) as i32;
+
+        if target == self.target {
+            return ViewAction::None;
+        }
+
+        self.target = target;
+        self.binding.dirty = true;
+
+        self.binding.command(CommandAction::Set(target))
+    }
+
+    fn fraction(&self, value: i32) -> f32 {
+        if self.max == self.min {
+            return 1.0;
+        }
+
+        (value - self.min) as f32 / (self.max - self.min) as f32
+    }
+}
+
+impl View for ThermostatView {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            binding: Binding::new(name),
+            mode: HvacMode::default(),
+            current: None,
+            target: DEFAULT_TARGET,
+            min: DEFAULT_MIN,
+            max: DEFAULT_MAX,
+        }
+    }
+
+    fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        let color = self.mode.color(theme);
+
+        display.draw(&dial::track(theme))?;
+        display.draw(&dial::value(theme, self.fraction(self.target), color))?;
+
+        if let Some(current) = self.current {
+            display.draw(&dial::marker(
+                theme,
+                self.fraction(current),
+                theme.foreground,
+            ))?;
+        }
+
+        let name = Text {
+            content: self.name.clone(),
+            x: dial::CENTER,
+            y: 70,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.caption_font,
+        }
+        .fit_to_circle(Overflow::Ellipsize, 24);
+
+        display.draw(&name)?;
+
+        display.draw(&Text {
+            content: tenths(self.target),
+            x: dial::CENTER,
+            y: 110,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.title_font,
+        })?;
+
+        display.draw(&Text {
+            content: String::from(self.mode.label()),
+            x: dial::CENTER,
+            y: 140,
+            alignment: Alignment::Center,
+            color,
+            size: theme.body_font,
+        })?;
+
+        if let Some(current) = self.current {
+            display.draw(&Text {
+                content: format!("Now {}", tenths(current)),
+                x: dial::CENTER,
+                y: 165,
+                alignment: Alignment::Center,
+                color: theme.muted,
+                size: theme.caption_font,
+            })?;
+        }
+
+        for (limit, x) in [
+            (self.min, dial::CENTER - LIMIT_OFFSET),
+            (self.max, dial::CENTER + LIMIT_OFFSET),
+        ] {
+            display.draw(&Text {
+                content: tenths(limit),
+                x,
+                y: LIMIT_Y,
+                alignment: Alignment::Center,
+                color: theme.muted,
+                size: theme.caption_font,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: InputEvent) -> ViewAction {
+        match event {
+            InputEvent::Press => self.cycle_mode(),
+            InputEvent::Rotate { delta, velocity } => self.rotate(delta, velocity),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn binding(&self) -> Option<&Binding> {
+        Some(&self.binding)
+    }
+
+    fn binding_mut(&mut self) -> Option<&mut Binding> {
+        Some(&mut self.binding)
+    }
+
+    fn on_entity_changed(&mut self, entity: &Entity) {
+        let state = &entity.state;
+        let mode = state
+            .mode
+            .as_deref()
+            .and_then(HvacMode::from_name)
+            .unwrap_or(self.mode);
+
+        self.set_state(
+            mode,
+            state.temperature.or(self.current),
+            state.target.unwrap_or(self.target),
+        );
+    }
+}
+
+fn tenths(value: i32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let value = value.unsigned_abs();
+
+    format!("{}{}.{}", sign, value / 10, value % 10)
+}
@@ -0,0 +1,3 @@
+mod value_picker;
+
+pub use self::value_picker::{PickerEvent, StepMode, ValuePicker};
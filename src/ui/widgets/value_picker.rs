@@ -0,0 +1,229 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use crate::{
+    peripherals::display::{
+        Display,
+        error::DisplayError,
+        graphics::{Alignment, Text},
+    },
+    ui::{InputEvent, Theme, views::dial},
+};
+
+// Coarse steps are this many fine steps unless set explicitly.
+const DEFAULT_COARSE_FACTOR: i32 = 10;
+// More decimals than this are not shown.
+const MAX_DECIMALS: u8 = 3;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepMode {
+    #[default]
+    Fine,
+    Coarse,
+}
+
+impl StepMode {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fine => "Fine",
+            Self::Coarse => "Coarse",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickerEvent {
+    ModeChanged(StepMode),
+    ValueChanged(i32),
+}
+
+// Number between `min` and `max` picked with the knob, for views that control a plain value such
+// as a fan speed or a timer. Turning moves it by the step of the current mode and pressing
+// switches between fine and coarse steps.
+//
+// Values are integers. With `decimals` set they are shown as fixed point, so a value of 215 with
+// one decimal reads "21.5".
+#[derive(Clone, Debug)]
+pub struct ValuePicker {
+    min: i32,
+    max: i32,
+    step: i32,
+    coarse_step: i32,
+    value: i32,
+    decimals: u8,
+    unit: String,
+    mode: StepMode,
+}
+
+impl ValuePicker {
+    pub fn new(min: i32, max: i32, step: i32) -> Self {
+        let step = step.max(1);
+
+        Self {
+            min: min.min(max),
+            max: max.max(min),
+            step,
+            coarse_step: step.saturating_mul(DEFAULT_COARSE_FACTOR),
+            value: min.min(max),
+            decimals: 0,
+            unit: String::new(),
+            mode: StepMode::default(),
+        }
+    }
+
+    pub fn with_value(mut self, value: i32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = unit.to_string();
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals.min(MAX_DECIMALS);
+        self
+    }
+
+    pub fn with_coarse_step(mut self, step: i32) -> Self {
+        self.coarse_step = step.max(self.step);
+        self
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn mode(&self) -> StepMode {
+        self.mode
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    // Sets the value, clamped to the range and rounded to the nearest step, and returns whether it
+    // changed.
+    pub fn set_value(&mut self, value: i32) -> bool {
+        let value = self.snap(value);
+
+        if value == self.value {
+            return false;
+        }
+
+        self.value = value;
+        true
+    }
+
+    pub fn set_mode(&mut self, mode: StepMode) {
+        self.mode = mode;
+    }
+
+    // Position of the value within the range, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.max == self.min {
+            return 1.0;
+        }
+
+        (self.value as i64 - self.min as i64) as f32 / (self.max as i64 - self.min as i64) as f32
+    }
+
+    // Value with its decimals and unit, such as "21.5°C".
+    pub fn format(&self) -> String {
+        let number = if self.decimals == 0 {
+            format!("{}", self.value)
+        } else {
+            let scale = 10i32.pow(self.decimals as u32);
+            let sign = if self.value < 0 { "-" } else { "" };
+            let value = self.value.unsigned_abs();
+
+            format!(
+                "{}{}.{:0width$}",
+                sign,
+                value / scale as u32,
+                value % scale as u32,
+                width = self.decimals as usize
+            )
+        };
+
+        format!("{}{}", number, self.unit)
+    }
+
+    // Applies an input event and reports what it changed, if anything. Other events are left for
+    // the view to handle.
+    pub fn handle_event(&mut self, event: InputEvent) -> Option<PickerEvent> {
+        match event {
+            InputEvent::Rotate { delta, velocity } => {
+                let moved = match self.mode {
+                    StepMode::Fine => dial::steps(delta, velocity).saturating_mul(self.step),
+                    StepMode::Coarse => delta.saturating_mul(self.coarse_step),
+                };
+
+                self.set_value(self.value.saturating_add(moved))
+                    .then_some(PickerEvent::ValueChanged(self.value))
+            },
+            InputEvent::Press => {
+                self.mode = match self.mode {
+                    StepMode::Fine => StepMode::Coarse,
+                    StepMode::Coarse => StepMode::Fine,
+                };
+
+                Some(PickerEvent::ModeChanged(self.mode))
+            },
+            _ => None,
+        }
+    }
+
+    // Draws the dial with the value filled in, the formatted value and the step mode, leaving the
+    // middle of the panel free for a label.
+    pub fn render(&self, display: &mut Display, theme: &Theme) -> Result<(), DisplayError> {
+        display.draw(&dial::track(theme))?;
+        display.draw(&dial::value(theme, self.fraction(), theme.accent))?;
+
+        display.draw(&Text {
+            content: self.format(),
+            x: dial::CENTER,
+            y: 105,
+            alignment: Alignment::Center,
+            color: theme.foreground,
+            size: theme.title_font,
+        })?;
+
+        let mode_color = match self.mode {
+            StepMode::Fine => theme.muted,
+            StepMode::Coarse => theme.accent,
+        };
+
+        display.draw(&Text {
+            content: String::from(self.mode.label()),
+            x: dial::CENTER,
+            y: 165,
+            alignment: Alignment::Center,
+            color: mode_color,
+            size: theme.caption_font,
+        })?;
+
+        Ok(())
+    }
+
+    // Worked out in i64, as the span of the range may not fit in an i32.
+    fn snap(&self, value: i32) -> i32 {
+        let (min, max, step) = (self.min as i64, self.max as i64, self.step as i64);
+        let value = (value as i64).clamp(min, max);
+        let snapped = min + (value - min + step / 2) / step * step;
+
+        // The top of the range is always reachable, even when it is not a whole number of steps.
+        if snapped > max || max - value < value - snapped {
+            self.max
+        } else {
+            snapped as i32
+        }
+    }
+}